@@ -1,34 +1,54 @@
-pub fn process_part1(input: &str) -> String {
+use aoclib::{parse::parse_token, Answer, ParseResult, Solution};
+
+fn parse_input(input: &str) -> ParseResult<Vec<u32>> {
     input.split("\n\n")
         .map(|elf_calories| {
             elf_calories
                 .lines()
-                .map(|calories| calories.parse::<u32>().unwrap())
-                .sum::<u32>()
+                .map(|calories| parse_token::<u32>(input, calories, "calories"))
+                .sum::<ParseResult<u32>>()
         })
+        .collect()
+}
+
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    Ok(parse_input(input)?
+        .iter()
         .max()
-        .unwrap()
-        .to_string()
+        .unwrap_or(&0)
+        .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let mut result = input.split("\n\n")
-        .map(|elf_calories| {
-            elf_calories
-                .lines()
-                .map(|calories| calories.parse::<u32>().unwrap())
-                .sum::<u32>()
-        })
-        .collect::<Vec<_>>();
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let mut result = parse_input(input)?;
 
     result.sort_by(|a,b| b.cmp(a));
 
-    result.iter()
+    Ok(result.iter()
         .take(3)
         .sum::<u32>()
-        .to_string()
+        .to_string())
+}
+
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 1;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
 }
 
+aoclib::expected_tests!(Day01);
 
 #[cfg(test)]
 mod tests {
@@ -51,7 +71,14 @@ mod tests {
 
 10000";
 
-        assert_eq!(process_part1(&input), "24000");
-        assert_eq!(process_part2(&input), "45000");
+        assert_eq!(process_part1(input).unwrap(), "24000");
+        assert_eq!(process_part2(input).unwrap(), "45000");
+    }
+
+    #[test]
+    fn test_invalid_calories() {
+        let err = process_part1("1000\n20x0\n").unwrap_err();
+
+        assert_eq!(err.to_string(), "line 2, column 1: expected calories, found `20x0`");
     }
 }
@@ -1,3 +1,4 @@
+use aoclib::{Answer, ParseError, ParseResult, Solution};
 use std::str::FromStr;
 use std::cmp::{PartialOrd, Ordering};
 
@@ -50,10 +51,26 @@ impl PartialOrd for Move {
     }
 }
 
-pub fn process_part1(input: &str) -> String {
+fn parse_input(input: &str) -> ParseResult<Vec<Vec<Move>>> {
     input.lines().map(|line| {
-        let curr_moves: Vec<Move> = line.split(" ").map(|the_move| the_move.parse::<Move>().unwrap()).collect();
-        
+        let curr_moves = line.split(" ")
+            .map(|the_move| {
+                the_move.parse::<Move>()
+                    .map_err(|_| ParseError::at(input, the_move, "A, B, C, X, Y or Z"))
+            })
+            .collect::<ParseResult<Vec<Move>>>()?;
+
+        if curr_moves.len() != 2 {
+            return Err(ParseError::at(input, line, "two moves separated by space"));
+        }
+
+        Ok(curr_moves)
+    })
+    .collect()
+}
+
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    Ok(parse_input(input)?.iter().map(|curr_moves| {
         let mut round_points = 0u32;
         if curr_moves[1] > curr_moves[0] {
             round_points += 6;
@@ -70,14 +87,12 @@ pub fn process_part1(input: &str) -> String {
         round_points
     })
     .sum::<u32>()
-    .to_string()
+    .to_string())
 }
 
 
-pub fn process_part2(input: &str) -> String {
-    input.lines().map(|line| {
-        let curr_moves: Vec<Move> = line.split(" ").map(|the_move| the_move.parse::<Move>().unwrap()).collect();
-
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    Ok(parse_input(input)?.iter().map(|curr_moves| {
         let mut round_points = 0u32;
         match curr_moves[1] {
             Move::Rock => match curr_moves[0] {
@@ -97,9 +112,28 @@ pub fn process_part2(input: &str) -> String {
         round_points
     })
     .sum::<u32>()
-    .to_string()
+    .to_string())
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 2;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
 }
 
+aoclib::expected_tests!(Day02);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,11 +143,11 @@ C Z";
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(INPUT), "15");
+        assert_eq!(process_part1(INPUT).unwrap(), "15");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(INPUT), "12");
+        assert_eq!(process_part2(INPUT).unwrap(), "12");
     }
 }
@@ -1,3 +1,5 @@
+use aoclib::{Answer, ParseError, ParseResult, Solution};
+
 
 const POINTS_ALPHABET: &str = "_abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
@@ -16,11 +18,26 @@ fn find_comon_in_groups(groups: &[String]) -> Option<char> {
         }
     }
 
-    return None
+    None
 }
 
-pub fn process_part1(input: &str) -> String {
+fn parse_input(input: &str) -> ParseResult<Vec<&str>> {
     input.lines().map(|line| {
+        if let Some(pos) = line.find(|c: char| !c.is_ascii_alphabetic()) {
+            return Err(ParseError::at(input, &line[pos..], "item letter"));
+        }
+
+        if line.is_empty() || !line.len().is_multiple_of(2) {
+            return Err(ParseError::at(input, line, "even number of items"));
+        }
+
+        Ok(line)
+    })
+    .collect()
+}
+
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    Ok(parse_input(input)?.iter().map(|line| {
         let line_chunks = line.chars()
             .collect::<Vec<_>>()
             .chunks(line.len()/2)
@@ -32,23 +49,42 @@ pub fn process_part1(input: &str) -> String {
             _ => 0,
         }
     })
-    .sum::<u32>().to_string()
+    .sum::<u32>().to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    input.lines()
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    Ok(parse_input(input)?.iter()
         .map(|line| line.to_string())
         .collect::<Vec<String>>()
         .chunks(3)
         .map(|line| {
-        match find_comon_in_groups(&line) {
+        match find_comon_in_groups(line) {
             Some(common_item) => POINTS_ALPHABET.to_string().find(common_item).unwrap_or(0) as u32,
             _ => 0,
         }
     })
-    .sum::<u32>().to_string()
+    .sum::<u32>().to_string())
 }
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 3;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
+}
+
+aoclib::expected_tests!(Day03);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,11 +97,11 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(INPUT), "157");
+        assert_eq!(process_part1(INPUT).unwrap(), "157");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(INPUT), "70");
+        assert_eq!(process_part2(INPUT).unwrap(), "70");
     }
 }
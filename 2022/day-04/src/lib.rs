@@ -1,61 +1,69 @@
-struct Assigment {
-    start: u32,
-    end: u32,
-}
+use aoclib::{parse::parse_token, Answer, ParseError, ParseResult, RangeSet, Solution};
+use std::ops::Range;
 
-impl Assigment {
-    pub fn new(range: &str) -> Self {
-        let bounds: Vec<u32> = range.split("-")
-            .map(|bound| bound.parse::<u32>().unwrap())
-            .collect();
-            
-
-        Assigment{
-            start: bounds[0],
-            end: bounds[1],
-        }
-    }
+// sections `2-4` are stored as `2..5`
+fn parse_assignment(input: &str, range: &str) -> ParseResult<Range<u32>> {
+    let (start_token, end_token) = range.split_once("-")
+        .ok_or_else(|| ParseError::at(input, range, "range like `2-4`"))?;
 
-    // TODO: should be checked if start < end... but i do not care
-    pub fn overlap_full(&self, other: &Self) -> bool {
-        self.start <= other.start && self.end >= other.end 
+    let start = parse_token(input, start_token, "section number")?;
+    let end: u32 = parse_token(input, end_token, "section number")?;
+    if start > end {
+        return Err(ParseError::at(input, range, "range starting at its lower section"));
     }
 
-    pub fn overlap_at_all(&self, other: &Self) -> bool {
-        self.overlap_full(other) 
-            || (self.start >= other.start && self.start <= other.end)
-            || (self.end <= other.end && self.end >= other.start)
-    }
+    let end = end.checked_add(1)
+        .ok_or_else(|| ParseError::at(input, end_token, format!("section number below {}", u32::MAX)))?;
+    Ok(start..end)
 }
 
-pub fn process_part1(input: &str) -> String {
+fn parse_input(input: &str) -> ParseResult<Vec<(RangeSet<u32>, RangeSet<u32>)>> {
     input.lines()
         .map(|line| {
-            let assigments: Vec<Assigment> = line.split(",")
-                .map(|assigment_str| Assigment::new(assigment_str))
-                .collect();
-            
-            assigments[0].overlap_full(&assigments[1]) || assigments[1].overlap_full(&assigments[0])
+            let (first, second) = line.split_once(",")
+                .ok_or_else(|| ParseError::at(input, line, "pair of ranges separated by `,`"))?;
+
+            Ok((
+                parse_assignment(input, first)?.into(),
+                parse_assignment(input, second)?.into(),
+            ))
         })
-        .map(|item| item as u32)
-        .sum::<u32>()
-        .to_string()
+        .collect()
 }
 
-pub fn process_part2(input: &str) -> String {
-    input.lines()
-    .map(|line| {
-        let assigments: Vec<Assigment> = line.split(",")
-            .map(|assigment_str| Assigment::new(assigment_str))
-            .collect();
-        
-        assigments[0].overlap_at_all(&assigments[1]) || assigments[1].overlap_at_all(&assigments[0])
-    })
-    .map(|item| item as u32)
-    .sum::<u32>()
-    .to_string()
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    Ok(parse_input(input)?.iter()
+        .filter(|(first, second)| first.is_subset(second) || second.is_subset(first))
+        .count()
+        .to_string())
+}
+
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    Ok(parse_input(input)?.iter()
+        .filter(|(first, second)| !first.intersection(second).is_empty())
+        .count()
+        .to_string())
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 4;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
 }
 
+aoclib::expected_tests!(Day04);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,11 +76,25 @@ mod tests {
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(INPUT), "2");
+        assert_eq!(process_part1(INPUT).unwrap(), "2");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(INPUT), "4");
+        assert_eq!(process_part2(INPUT).unwrap(), "4");
+    }
+
+    #[test]
+    fn invalid_range() {
+        let err = process_part1("2-4,6-8\n2-3;4-5").unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "pair of ranges separated by `,`");
+
+        let err = process_part1("2-4,6-8\n4-2,1-9").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: expected range starting at its lower section, found `4-2`");
+
+        let err = process_part2("1-4294967295,2-3").unwrap_err();
+        assert_eq!(err.expected, "section number below 4294967295");
     }
 }
@@ -1,3 +1,4 @@
+use aoclib::{parse::parse_token, Answer, ParseError, ParseResult, Solution};
 use std::vec::Vec;
 use std::collections::VecDeque;
 
@@ -22,13 +23,13 @@ fn parse_crates(input: &str) -> Vec<VecDeque<char>> {
                         crates_stacks.push(VecDeque::new());
                     }
 
-                    if crate_id >= 'A' && crate_id <= 'Z' {
+                    if crate_id.is_ascii_uppercase() {
                         crates_stacks[crate_no].push_back(crate_id);
                     }
                 })
         });
    
-    return crates_stacks
+    crates_stacks
 }
 
 #[derive(Debug)]
@@ -38,27 +39,40 @@ struct Move {
     to: usize,
 }
 
-fn parse_moves(input: &str) -> Vec<Move> {
-    let input_parts = input.split("\n\n").collect::<Vec<&str>>();
-    input_parts[1].lines()
-        .map(|line| {
-            let parts = line.split(" from ").map(|x| x.to_string()).collect::<Vec<String>>();
-            let parts_from_to = parts[1].split(" to ").collect::<Vec<&str>>();
+fn parse_moves(input: &str, stacks: usize) -> ParseResult<Vec<Move>> {
+    let (_, moves_section) = input.split_once("\n\n")
+        .ok_or_else(|| ParseError::at(input, &input[input.len()..], "empty line before moves"))?;
 
-            Move{
-                amount: (&parts[0][5..]).parse::<u32>().unwrap(),
-                from: parts_from_to[0].parse::<usize>().unwrap(),
-                to: parts_from_to[1].parse::<usize>().unwrap(),
-            }
+    moves_section.lines()
+        .map(|line| {
+            let malformed = || ParseError::at(input, line, "move like `move 1 from 2 to 1`");
+            let (amount, from_to) = line.strip_prefix("move ")
+                .and_then(|rest| rest.split_once(" from "))
+                .ok_or_else(malformed)?;
+            let (from, to) = from_to.split_once(" to ").ok_or_else(malformed)?;
+
+            let stack = |token: &str| -> ParseResult<usize> {
+                match parse_token(input, token, "stack number")? {
+                    number @ 1.. if number <= stacks => Ok(number),
+                    _ => Err(ParseError::at(input, token, format!("stack number between 1 and {}", stacks))),
+                }
+            };
+
+            Ok(Move{
+                amount: parse_token(input, amount, "crate amount")?,
+                from: stack(from)?,
+                to: stack(to)?,
+            })
         })
-        .collect::<Vec<Move>>()
+        .collect()
 }
 
 
-pub fn process_part1(input: &str) -> String {
+pub fn process_part1(input: &str) -> ParseResult<String> {
     let mut crates_stack = parse_crates(input);
+    let moves = parse_moves(input, crates_stack.len())?;
 
-    parse_moves(input).iter().for_each(|item| {
+    moves.iter().for_each(|item| {
         (0..item.amount).for_each(|_| {
             if let Some(crate_id) = crates_stack[item.from-1].pop_front() {
                 crates_stack[item.to-1].push_front(crate_id)
@@ -66,13 +80,14 @@ pub fn process_part1(input: &str) -> String {
         })
     });
 
-    crates_stack.iter().map(|crates| crates[0]).collect::<String>()
+    Ok(crates_stack.iter().map(|crates| crates[0]).collect::<String>())
 }
 
-pub fn process_part2(input: &str) -> String {
+pub fn process_part2(input: &str) -> ParseResult<String> {
     let mut crates_stack = parse_crates(input);
+    let moves = parse_moves(input, crates_stack.len())?;
 
-    parse_moves(input).iter().for_each(|item| {
+    moves.iter().for_each(|item| {
         crates_stack[item.from-1].rotate_left(item.amount as usize);
         (0..item.amount).rev().for_each(|_| {
             if let Some(crate_id) = crates_stack[item.from-1].pop_back() {
@@ -81,9 +96,28 @@ pub fn process_part2(input: &str) -> String {
         })
     });
 
-    crates_stack.iter().map(|crates| crates[0]).collect::<String>()
+    Ok(crates_stack.iter().map(|crates| crates[0]).collect::<String>())
 }
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 5;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
+}
+
+aoclib::expected_tests!(Day05);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,11 +133,20 @@ move 1 from 1 to 2";
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(INPUT), "CMZ");
+        assert_eq!(process_part1(INPUT).unwrap(), "CMZ");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(INPUT), "MCD");
+        assert_eq!(process_part2(INPUT).unwrap(), "MCD");
+    }
+
+    #[test]
+    fn unknown_stack() {
+        let input = INPUT.replace("move 1 from 1 to 2", "move 1 from 1 to 4");
+        let err = process_part1(&input).unwrap_err();
+
+        assert_eq!((err.line, err.column), (9, 18));
+        assert_eq!(err.expected, "stack number between 1 and 3");
     }
 }
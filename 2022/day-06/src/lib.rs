@@ -1,40 +1,90 @@
-use std::collections::HashSet;
+use aoclib::{Answer, CircularBuffer, ParseError, ParseResult, Solution};
 
 fn process_unique_seq(input: &str, seq_len: usize) -> u32 {
-    for char_no in 0..(input.len()-seq_len-1) {
-        if input.get(char_no..char_no+seq_len).unwrap().chars().collect::<HashSet<char>>().len() == seq_len {
-            return (char_no + seq_len) as u32
+    let mut window = CircularBuffer::new(seq_len);
+    // occurrences of every letter in the window
+    let mut counts = [0; 26];
+    let mut distinct = 0;
+
+    for (char_no, c) in input.bytes().enumerate() {
+        let letter = (c - b'a') as usize;
+        counts[letter] += 1;
+        if counts[letter] == 1 {
+            distinct += 1;
+        }
+
+        if let Some(old) = window.push(letter) {
+            counts[old] -= 1;
+            if counts[old] == 0 {
+                distinct -= 1;
+            }
+        }
+
+        if distinct == seq_len {
+            return (char_no + 1) as u32
         }
     }
-    return 0
+    0
+}
+
+fn parse_input(input: &str, seq_len: usize) -> ParseResult<&str> {
+    let signal = input.trim_end();
+
+    if let Some(pos) = signal.find(|c: char| !c.is_ascii_lowercase()) {
+        return Err(ParseError::at(input, &signal[pos..pos+1], "lowercase letter"));
+    }
+    if signal.len() <= seq_len + 1 {
+        return Err(ParseError::at(input, &signal[signal.len()..], format!("more than {} characters", seq_len + 1)));
+    }
+
+    Ok(signal)
+}
+
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    Ok(process_unique_seq(parse_input(input, 4)?, 4).to_string())
 }
 
-pub fn process_part1(input: &str) -> String {
-    process_unique_seq(input, 4).to_string()
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    Ok(process_unique_seq(parse_input(input, 14)?, 14).to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    process_unique_seq(input, 14).to_string()
+pub struct Day06;
+
+impl Solution for Day06 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 6;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
 }
 
+aoclib::expected_tests!(Day06);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1("bvwbjplbgvbhsrlpgdmjqwftvncz"), "5");
-        assert_eq!(process_part1("nppdvjthqldpwncqszvftbrmjlhg"), "6");
-        assert_eq!(process_part1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), "10");
-        assert_eq!(process_part1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), "11");
+        assert_eq!(process_part1("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), "5");
+        assert_eq!(process_part1("nppdvjthqldpwncqszvftbrmjlhg").unwrap(), "6");
+        assert_eq!(process_part1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(), "10");
+        assert_eq!(process_part1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(), "11");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), "19");
-        assert_eq!(process_part2("bvwbjplbgvbhsrlpgdmjqwftvncz"), "23");
-        assert_eq!(process_part2("nppdvjthqldpwncqszvftbrmjlhg"), "23");
-        assert_eq!(process_part2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), "29");
-        assert_eq!(process_part2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), "26");
+        assert_eq!(process_part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(), "19");
+        assert_eq!(process_part2("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), "23");
+        assert_eq!(process_part2("nppdvjthqldpwncqszvftbrmjlhg").unwrap(), "23");
+        assert_eq!(process_part2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(), "29");
+        assert_eq!(process_part2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(), "26");
     }
 }
@@ -1,3 +1,4 @@
+use aoclib::{parse::parse_token, Answer, ParseError, ParseResult, Solution};
 use std::vec::Vec;
 
 #[derive(Debug)]
@@ -7,6 +8,7 @@ enum ChangeDir {
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 enum File {
     File{ size: usize, name: String },
     Dir(String),
@@ -14,7 +16,6 @@ enum File {
 
 #[derive(Debug)]
 enum Command {
-    Unknown,
     Cd(ChangeDir),
     Ls,
 }
@@ -26,98 +27,123 @@ enum TerminalLine {
     File(File),
 }
 
-fn parse_commands(input: &str) -> Vec<TerminalLine> {
+fn parse_commands(input: &str) -> ParseResult<Vec<TerminalLine>> {
     input.lines().map(|line| {
-        if line.get(0..1).unwrap_or("") == "$" {
-            let cmd_parts = line.get(2..).unwrap().split(" ").collect::<Vec<&str>>();
-
-            return TerminalLine::Command(match cmd_parts[0] {
-                    "ls" => Command::Ls,
-                    "cd" => Command::Cd(match cmd_parts[1] {
-                        ".." => ChangeDir::Down,
-                        child_name => ChangeDir::Up(child_name.to_string()),
-                    }),
-                    _ => Command::Unknown,
-                });
-        } 
-        else {
-            let file_parts = line.split(" ").collect::<Vec<&str>>();
-            if file_parts[0] == "dir" {
-                return TerminalLine::File(File::Dir(file_parts[1].to_string()))
-            } else if file_parts.len() == 2 && file_parts[0].parse::<u32>().is_ok() {
-                return TerminalLine::File(File::File { size: file_parts[0].parse::<usize>().unwrap(), name: file_parts[1].to_string() });
-            }
+        if line.is_empty() {
+            return Ok(TerminalLine::Empty);
+        }
+
+        if let Some(command) = line.strip_prefix("$ ") {
+            return Ok(TerminalLine::Command(match command.split_once(" ") {
+                None if command == "ls" => Command::Ls,
+                Some(("cd", "..")) => Command::Cd(ChangeDir::Down),
+                Some(("cd", child_name)) if !child_name.is_empty() => Command::Cd(ChangeDir::Up(child_name.to_string())),
+                _ => return Err(ParseError::at(input, command, "`ls` or `cd <dir>`")),
+            }));
+        }
+
+        match line.split_once(" ") {
+            Some(("dir", name)) => Ok(TerminalLine::File(File::Dir(name.to_string()))),
+            Some((size, name)) => Ok(TerminalLine::File(File::File {
+                size: parse_token(input, size, "file size or `dir`")?,
+                name: name.to_string(),
+            })),
+            None => Err(ParseError::at(input, line, "command or directory listing")),
         }
-        
-        TerminalLine::Empty
     })
-    .collect::<Vec<TerminalLine>>()
+    .collect()
 }
 
-fn compute_dirs_size(input: &str) -> Vec<(String, usize)> {
-    let terminal_lines = parse_commands(input);
+fn compute_dirs_size(input: &str) -> ParseResult<Vec<(String, usize)>> {
+    let terminal_lines = parse_commands(input)?;
     let mut analyzed_dirs: Vec<(String, usize)> = Vec::new();
     let mut cwd: Vec<(String, usize)> = Vec::new();
 
-    terminal_lines.iter().for_each(|line| {
+    // parse_commands gives one terminal line per input line
+    for (line, text) in terminal_lines.iter().zip(input.lines()) {
         match line {
-            TerminalLine::Command(cmd) => match cmd {
-                Command::Cd(change_dir) => match change_dir {
-                    ChangeDir::Up(name) => {
-                        cwd.push((name.to_string(), 0));
-                    }
-                    ChangeDir::Down => {
-                        let current_dir = cwd.pop().unwrap();
-                        analyzed_dirs.push(current_dir);
+            TerminalLine::Command(Command::Cd(change_dir)) => match change_dir {
+                ChangeDir::Up(name) => {
+                    cwd.push((name.to_string(), 0));
+                }
+                ChangeDir::Down => {
+                    if cwd.len() < 2 {
+                        return Err(ParseError::at(input, &text[text.len() - 2..], "`cd ..` below the top directory"));
                     }
+                    let current_dir = cwd.pop().unwrap();
+                    analyzed_dirs.push(current_dir);
                 }
-                _ => {}
-            }
-            TerminalLine::File(f) => match f {
-                File::File { size, name } => {
-                    cwd.iter_mut().for_each(|dir| dir.1 += size);
-                },
-                _ => {},
-            }
-            TerminalLine::Empty => {},
+            },
+            TerminalLine::File(File::File { size, .. }) => {
+                cwd.iter_mut().for_each(|dir| dir.1 += size);
+            },
+            _ => {},
         }
-    });
+    }
 
     analyzed_dirs.append(&mut cwd);
 
-    analyzed_dirs
+    if analyzed_dirs.is_empty() {
+        return Err(ParseError::at(input, &input[input.len()..], "`$ cd <dir>`"));
+    }
+
+    Ok(analyzed_dirs)
 }
 
-pub fn process_part1(input: &str) -> String {
-    compute_dirs_size(input).iter().map(|dir| {
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    Ok(compute_dirs_size(input)?.iter().map(|dir| {
         if dir.1 < 100000 { dir.1 } else { 0 }
-    }).sum::<usize>().to_string()
+    }).sum::<usize>().to_string())
 }
 
 #[allow(unused)]
-pub fn process_part2(input: &str) -> String {
+pub fn process_part2(input: &str) -> ParseResult<String> {
     const FILESYSTEM_SPACE: usize = 70000000;
     const REQUIRED_SPACE: usize = 30000000;
 
-    let mut directory_sizes = compute_dirs_size(input)
+    let mut directory_sizes = compute_dirs_size(input)?
         .iter()
         .map(|dir| dir.1)
         .collect::<Vec<usize>>();
     
     directory_sizes.sort();
 
+    // compute_dirs_size gives at least the top directory, the largest one
     let root_dir_size = directory_sizes.iter().last().unwrap();
 
-    let required_space: usize = REQUIRED_SPACE - (FILESYSTEM_SPACE - root_dir_size);
+    let required_space: usize = REQUIRED_SPACE.saturating_sub(FILESYSTEM_SPACE.saturating_sub(*root_dir_size));
+    if required_space == 0 {
+        return Ok("0".to_string())
+    }
+
     for size in directory_sizes {
         if size > required_space {
-            return size.to_string()
+            return Ok(size.to_string())
         }
     }
 
-    "0".to_string()
+    Ok("0".to_string())
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 7;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
+}
+
+aoclib::expected_tests!(Day07);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,11 +173,30 @@ $ ls
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(INPUT), "95437");
+        assert_eq!(process_part1(INPUT).unwrap(), "95437");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(INPUT), "24933642");
+        assert_eq!(process_part2(INPUT).unwrap(), "24933642");
+    }
+
+    #[test]
+    fn unknown_command() {
+        let err = process_part1("$ cd /\n$ pwd").unwrap_err();
+
+        assert_eq!(err.to_string(), "line 2, column 3: expected `ls` or `cd <dir>`, found `pwd`");
+    }
+
+    #[test]
+    fn malformed_session() {
+        let err = process_part1("").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: expected `$ cd <dir>`, found end of input");
+
+        let err = process_part2("$ cd /\n$ cd ..").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 6: expected `cd ..` below the top directory, found `..`");
+
+        // the filesystem has enough free space already
+        assert_eq!(process_part2("$ cd /\n$ ls\n100 a").unwrap(), "0");
     }
 }
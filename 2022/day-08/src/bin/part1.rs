@@ -1,8 +0,0 @@
-use std::fs;
-
-use day_08::process_part1;
-
-fn main() {
-    let input = fs::read_to_string("input.txt").unwrap();
-    println!("{}", process_part1(&input));
-}
\ No newline at end of file
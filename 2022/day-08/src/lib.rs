@@ -1,106 +1,92 @@
+use aoclib::{Answer, Grid, ParseError, ParseResult, Solution, Vec2};
 
-pub fn process_part1(input: &str) -> String {
-    let cols = input.lines().last().unwrap().chars().map(|_| 1).sum::<usize>();
-    let lines = input.lines().map(|_| 1).sum::<usize>();
-
-    let input_rows = input
-        .lines()
-        .map(|row| row.chars().map(|num| num.to_digit(10).unwrap() as usize)
-        .collect::<Vec<usize>>())
-        .collect::<Vec<Vec<usize>>>();
-
-    let input_cols = (0..cols)
-        .map(|col| {
-            input_rows.iter()
-                .map(|line| line[col])
-                .collect::<Vec<usize>>()
+type Line<'a> = Vec<(Vec2, &'a usize)>;
+
+fn parse_input(input: &str) -> ParseResult<Grid<usize>> {
+    let grid = Grid::try_from_string_with_map(
+        input,
+        "tree height digit",
+        |c| c.to_digit(10).map(|height| height as usize),
+    )?;
+
+    if grid.size().x < 3 || grid.size().y < 3 {
+        return Err(ParseError::at(input, &input[input.len()..], "grid at least 3x3"));
+    }
+
+    Ok(grid)
+}
+
+// heights seen from the tree at `pos` looking up, down, left and right, the nearest first
+fn sight_lines(rows: &[Line], cols: &[Line], pos: Vec2) -> [Vec<usize>; 4] {
+    let (x, y) = (pos.x as usize, pos.y as usize);
+    let heights = |line: &[(Vec2, &usize)]| line.iter().map(|(_, &height)| height).collect::<Vec<usize>>();
+
+    [
+        heights(&cols[x][..y]).into_iter().rev().collect(),
+        heights(&cols[x][y+1..]),
+        heights(&rows[y][..x]).into_iter().rev().collect(),
+        heights(&rows[y][x+1..]),
+    ]
+}
+
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let grid = parse_input(input)?;
+    let rows = grid.rows().collect::<Vec<Line>>();
+    let cols = grid.cols().collect::<Vec<Line>>();
+
+    // trees on the edge have an empty line of sight, so they are always visible
+    Ok(grid
+        .map(|(pos, &current_height)| {
+            sight_lines(&rows, &cols, pos)
+                .iter()
+                .any(|line| line.iter().all(|&height| height < current_height))
         })
-        .collect::<Vec<Vec<usize>>>();
-
-    let visible_trees = (1..cols-1).map(|col| {
-        (1..lines-1).map(|row| {
-            let current_height = input_rows[row][col];
-
-            let lower_in_row = input_rows[row].iter().map(|&height| height<current_height).collect::<Vec<bool>>();
-            let lower_in_col = input_cols[col].iter().map(|&height| height<current_height).collect::<Vec<bool>>();
-
-            if lower_in_col[0..row].iter().all(|&lower| lower) {
-                return 1
-            } else if lower_in_col[row+1..].iter().all(|&lower| lower) {
-                return 1
-            } else if lower_in_row[0..col].iter().all(|&lower| lower) {
-                return 1
-            } else if lower_in_row[col+1..].iter().all(|&lower| lower) {
-                return 1
-            }
-           0
-        }).sum::<usize>()
-    }).sum::<usize>(); 
-
-    (visible_trees + 2*cols + 2*lines - 4).to_string()
+        .filter(|&visible| visible)
+        .count()
+        .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let cols = input.lines().last().unwrap().chars().map(|_| 1).sum::<usize>();
-    let lines = input.lines().map(|_| 1).sum::<usize>();
-
-    let input_rows = input
-        .lines()
-        .map(|row| row.chars().map(|num| num.to_digit(10).unwrap() as usize)
-        .collect::<Vec<usize>>())
-        .collect::<Vec<Vec<usize>>>();
-
-    let input_cols = (0..cols)
-        .map(|col| {
-            input_rows.iter()
-                .map(|line| line[col])
-                .collect::<Vec<usize>>()
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let grid = parse_input(input)?;
+    let rows = grid.rows().collect::<Vec<Line>>();
+    let cols = grid.cols().collect::<Vec<Line>>();
+
+    Ok(grid
+        .map(|(pos, &current_height)| {
+            sight_lines(&rows, &cols, pos)
+                .iter()
+                .map(|line| {
+                    // up to and including the first tree which is not lower
+                    line.iter()
+                        .position(|&height| height >= current_height)
+                        .map_or(line.len(), |pos| pos + 1)
+                })
+                .product::<usize>()
         })
-        .collect::<Vec<Vec<usize>>>();
-
-    (1..cols-1).map(|col| {
-        (1..lines-1).map(|row| {
-            let current_height = input_rows[row][col];
-
-            let mut score = (0usize, 0usize, 0usize, 0usize);
-
-            // top
-            for i in (0..row).rev() {
-                score.0 += 1;
-                if input_cols[col][i] >= current_height {
-                    break;
-                }
-            }
-            
-            // bottom
-            for i in row+1..input_cols[col].len() {
-                score.1 += 1;
-                if input_cols[col][i] >= current_height {
-                    break;
-                }
-            }
-
-            // left
-            for i in (0..col).rev() {
-                score.2 += 1;
-                if input_rows[row][i] >= current_height {
-                    break;
-                }
-            }
-
-            // right
-            for i in col+1..input_cols[row].len() {
-                score.3 += 1;
-                if input_rows[row][i] >= current_height {
-                    break;
-                }
-            }
-
-            score.0*score.1*score.2*score.3
-        }).max().unwrap()
-    }).max().unwrap().to_string()
+        .max()
+        .unwrap()
+        .to_string())
 }
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 8;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
+}
+
+aoclib::expected_tests!(Day08);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,11 +98,11 @@ mod tests {
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(INPUT), "21");
+        assert_eq!(process_part1(INPUT).unwrap(), "21");
     }
 
     #[test]
     fn part2() {
-        assert_eq!(process_part2(INPUT), "8");
+        assert_eq!(process_part2(INPUT).unwrap(), "8");
     }
 }
@@ -1,159 +1,109 @@
-use std::collections::HashSet;
-
-#[derive(Clone)]
-enum Move {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-fn compute_tail_position(current_tail_pos: (u32, u32), new_head_pos: (u32, u32)) -> (u32, u32) {
-    let is_connected = |t1: (u32, u32), t2: (u32, u32)| {
-        let distance = i32::abs(t1.0 as i32 - t2.0 as i32) + i32::abs(t1.1 as i32 - t2.1 as i32);
-        t1 == t2 || distance == 1 || (
-            current_tail_pos.0 != new_head_pos.0 
-            && current_tail_pos.1 != new_head_pos.1 
-            && distance == 2 
-        )
-    };
-
-    if is_connected(new_head_pos, current_tail_pos) {
-        return current_tail_pos
-    }
-
-    if new_head_pos.0 == current_tail_pos.0 {
-        if new_head_pos.1 < current_tail_pos.1 {
-            return (current_tail_pos.0, current_tail_pos.1-1); // left
-        } else {
-            return (current_tail_pos.0, current_tail_pos.1+1); // left
-        }
+use aoclib::{parse::parse_token, Answer, Direction, ParseError, ParseResult, Solution, SparseGrid, Vec2};
+
+fn parse_moves(input: &str) -> ParseResult<Vec<Direction>> {
+    let mut moves = vec![];
+
+    for line in input.lines() {
+        let (direction, steps) = line.split_once(" ")
+            .ok_or_else(|| ParseError::at(input, line, "direction and steps separated by space"))?;
+        let m = match direction {
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            "R" => Direction::Right,
+            "L" => Direction::Left,
+            _ => return Err(ParseError::at(input, direction, "U, D, R or L")),
+        };
+        let steps: usize = parse_token(input, steps, "number of steps")?;
+        moves.extend(vec![m; steps]);
     }
 
-    if new_head_pos.1 == current_tail_pos.1 {
-        if new_head_pos.0 < current_tail_pos.0 {
-            return (current_tail_pos.0-1, current_tail_pos.1); // up
-        } else {
-            return (current_tail_pos.0+1, current_tail_pos.1); // down
-        }
-    }
+    Ok(moves)
+}
 
-    // diagonal moves
-    if is_connected(new_head_pos, (current_tail_pos.0-1, current_tail_pos.1-1)) {
-        return (current_tail_pos.0-1, current_tail_pos.1-1);
-    } else if is_connected(new_head_pos, (current_tail_pos.0-1, current_tail_pos.1+1)) {
-        return (current_tail_pos.0-1, current_tail_pos.1+1);
-    } else if is_connected(new_head_pos, (current_tail_pos.0+1, current_tail_pos.1-1)) {
-        return (current_tail_pos.0+1, current_tail_pos.1-1);
-    } else if is_connected(new_head_pos, (current_tail_pos.0+1, current_tail_pos.1+1)) {
-        return (current_tail_pos.0+1, current_tail_pos.1+1);
+fn compute_tail_position(current_tail_pos: Vec2, new_head_pos: Vec2) -> Vec2 {
+    // touching, also diagonally
+    if current_tail_pos.chebyshev(&new_head_pos) <= 1 {
+        return current_tail_pos
     }
 
-    panic!("Unexpected move");
+    // one step towards the head, diagonal when they share neither row nor column
+    current_tail_pos + (new_head_pos - current_tail_pos).signum()
 }
 
-pub fn process_part1(input: &str) -> String {
-    let mut tail_positions: HashSet<(u32, u32)> = HashSet::new();
-    let mut current_head_position = (1000u32, 1000u32);
-    let mut current_tail_position = (1000u32, 1000u32);
-
-    input.lines().map(|line| {
-        let move_instructions = line.split(" ").collect::<Vec<&str>>();
-        return match move_instructions[0] {
-            "U" => vec![Move::Up; move_instructions[1].parse::<usize>().unwrap()],
-            "D" => vec![Move::Down; move_instructions[1].parse::<usize>().unwrap()],
-            "R" => vec![Move::Right; move_instructions[1].parse::<usize>().unwrap()],
-            "L" => vec![Move::Left; move_instructions[1].parse::<usize>().unwrap()],
-            _ => vec![],
-        }
-    })
-    .flatten()
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let mut tail_positions: SparseGrid<()> = SparseGrid::new();
+    let mut current_head_position = Vec2{x: 0, y: 0};
+    let mut current_tail_position = Vec2{x: 0, y: 0};
+
+    parse_moves(input)?.into_iter()
     .for_each(|m| {
-        match m {
-            Move::Up => current_head_position.1 -= 1,
-            Move::Down => current_head_position.1 += 1,
-            Move::Left => current_head_position.0 -= 1,
-            Move::Right => current_head_position.0 += 1,
-        }
+        current_head_position += m.offset();
         
         current_tail_position = compute_tail_position(current_tail_position, current_head_position);
-        tail_positions.insert(current_tail_position.clone());
+        tail_positions.put_at(current_tail_position, ());
     });
     
-    tail_positions.len().to_string()
+    Ok(tail_positions.len().to_string())
 }
 
 #[allow(unused)]
-fn print(rope: &Vec<(u32, u32)>, width: usize, height: usize) {
-    let mut buffer = vec!['.'; width*height];
-    
-    let calc_pos = |knot: &(u32, u32)| -> usize {
-        return (width*knot.1 as usize + knot.0 as usize);
-    };
-
+fn print(rope: &[Vec2]) {
+    let mut board = SparseGrid::new();
+    board.put_at(Vec2{x: 0, y: 0}, 's');
 
     for (idx, knot_pos) in rope.iter().enumerate().rev() {
-        if idx == 0 {
-            buffer[calc_pos(knot_pos)] = 'H';
-        } else if idx == 9 {
-            buffer[calc_pos(knot_pos)] = 'T';
-        } else {
-            buffer[calc_pos(knot_pos)] = (48 + idx as u8) as char;
-        }
+        let label = match idx {
+            0 => 'H',
+            9 => 'T',
+            _ => (48 + idx as u8) as char,
+        };
+        board.put_at(*knot_pos, label);
     }
 
-    println!("{:?}", rope);
-    for (pos, ch) in buffer.iter().enumerate() {
-        print!("{}", ch);
-
-        if pos % width == 0 {
-            println!("");
-        }
-    }
-
-    println!("\n\n\n");
+    println!("{}\n\n\n", board.visualization('.', |c| *c));
 }
 
 
-pub fn process_part2(input: &str) -> String {
-    let mut tail_positions: HashSet<(u32, u32)> = HashSet::new();
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let mut tail_positions: SparseGrid<()> = SparseGrid::new();
 
-    let mut rope_position: Vec<(u32, u32)> = vec![(1000u32, 1000u32); 10];
+    let mut rope_position: Vec<Vec2> = vec![Vec2{x: 0, y: 0}; 10];
     const HEAD_ID: usize = 0;
     const TAIL_ID: usize = 9;
 
-    input.lines().map(|line| {
-        let move_instructions = line.split(" ").collect::<Vec<&str>>();
-        return match move_instructions[0] {
-            "U" => vec![Move::Up; move_instructions[1].parse::<usize>().unwrap()],
-            "D" => vec![Move::Down; move_instructions[1].parse::<usize>().unwrap()],
-            "R" => vec![Move::Right; move_instructions[1].parse::<usize>().unwrap()],
-            "L" => vec![Move::Left; move_instructions[1].parse::<usize>().unwrap()],
-            _ => vec![],
-        }
-    })
-    .flatten()
+    parse_moves(input)?.into_iter()
     .for_each(|m| {
-        let mut head_position = rope_position[HEAD_ID];
-        match m {
-            Move::Up => head_position.1 -= 1,
-            Move::Down => head_position.1 += 1,
-            Move::Left => head_position.0 -= 1,
-            Move::Right => head_position.0 += 1,
-        }
-
-        rope_position[HEAD_ID] = head_position;
+        rope_position[HEAD_ID] += m.offset();
         for (knot_idx, _knot_position) in rope_position.clone().iter().enumerate().skip(1) {
             rope_position[knot_idx] = compute_tail_position(rope_position[knot_idx], rope_position[knot_idx-1]);
         }
         
-        // print(&rope_position, 33, 33);
-        tail_positions.insert(rope_position[TAIL_ID].clone());
+        // print(&rope_position);
+        tail_positions.put_at(rope_position[TAIL_ID], ());
     });
     
-    tail_positions.len().to_string()
+    Ok(tail_positions.len().to_string())
+}
+
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 9;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
 }
 
+aoclib::expected_tests!(Day09);
 
 #[cfg(test)]
 mod tests {
@@ -169,7 +119,7 @@ R 2";
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(INPUT), "13");
+        assert_eq!(process_part1(INPUT).unwrap(), "13");
     }
 
     #[test]
@@ -183,7 +133,7 @@ D 10
 L 25
 U 20";
 
-        // assert_eq!(process_part2(INPUT), "1");
-       assert_eq!(process_part2(INPUT2), "36");
+        // assert_eq!(process_part2(INPUT).unwrap(), "1");
+       assert_eq!(process_part2(INPUT2).unwrap(), "36");
     }
 }
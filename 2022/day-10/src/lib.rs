@@ -1,3 +1,4 @@
+use aoclib::{parse::parse_token, Answer, ParseError, ParseResult, Solution};
 use std::vec;
 
 #[derive(Debug)]
@@ -7,36 +8,40 @@ enum Mnemonic {
 }
 
 #[derive(Debug)]
-struct CPU {
+struct Cpu {
     cycle: u32,
     reg_x: i32,
     signal_strength: i64,
     crt: Vec<char>,
 }
 
-pub fn process_part1(input: &str) -> String {
-    let mut cpu = CPU{
+fn parse_program(input: &str) -> ParseResult<Vec<Mnemonic>> {
+    let mut program = vec![];
+
+    for line in input.lines() {
+        match line.split_once(" ") {
+            None if line == "noop" => program.push(Mnemonic::Noop),
+            Some(("addx", value)) => program.extend([Mnemonic::Noop, Mnemonic::AddX(parse_token(input, value, "addx operand")?)]),
+            _ => return Err(ParseError::at(input, line, "`noop` or `addx <value>`")),
+        }
+    }
+
+    Ok(program)
+}
+
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let mut cpu = Cpu{
         cycle: 0,
         reg_x: 1,
         signal_strength: 0,
         crt: vec![],
     };
 
-    input.lines()
-    .map(|line| {
-        let instruction = line.split(" ").collect::<Vec<&str>>();
-
-        return match instruction[0] {
-            "noop" => vec![Mnemonic::Noop],
-            "addx" => vec![Mnemonic::Noop, Mnemonic::AddX(instruction[1].parse::<i32>().unwrap())],
-            _ => panic!("Invalid instruction"),
-        };
-    })
-    .flatten()
+    parse_program(input)?.into_iter()
     .for_each(|mnemonic| {
         cpu.cycle+=1;
 
-        if cpu.cycle == 20 || (20+cpu.cycle)%40 == 0{
+        if cpu.cycle == 20 || (20 + cpu.cycle).is_multiple_of(40){
             println!("{} * {} = {}", cpu.cycle, cpu.reg_x, cpu.cycle as i64 * cpu.reg_x as i64);
             cpu.signal_strength += cpu.cycle as i64 * cpu.reg_x as i64;
         }
@@ -50,28 +55,18 @@ pub fn process_part1(input: &str) -> String {
     });
 
 
-    cpu.signal_strength.to_string()
+    Ok(cpu.signal_strength.to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let mut cpu = CPU{
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let mut cpu = Cpu{
         cycle: 0,
         reg_x: 1,
         signal_strength: 0,
         crt: vec![],
     };
 
-    input.lines()
-    .map(|line| {
-        let instruction = line.split(" ").collect::<Vec<&str>>();
-
-        return match instruction[0] {
-            "noop" => vec![Mnemonic::Noop],
-            "addx" => vec![Mnemonic::Noop, Mnemonic::AddX(instruction[1].parse::<i32>().unwrap())],
-            _ => panic!("Invalid instruction"),
-        };
-    })
-    .flatten()
+    parse_program(input)?.into_iter()
     .for_each(|mnemonic| {
         if [cpu.reg_x-1, cpu.reg_x, cpu.reg_x+1].contains(&((cpu.cycle % 40) as i32)) {
             cpu.crt.push('#');
@@ -89,16 +84,35 @@ pub fn process_part2(input: &str) -> String {
         }
     });
     
-    cpu.crt.iter().enumerate()
+    Ok(cpu.crt.iter().enumerate()
     .map(|(idx, c)| {
         if idx > 30 && (idx+1)%40 == 0 {
-            return format!("{c}\n");
+            format!("{c}\n")
         } else {
-            return c.to_string();
+            c.to_string()
         }
-    }).collect::<String>()
+    }).collect::<String>())
+}
+
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 10;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
 }
 
+aoclib::expected_tests!(Day10);
 
 #[cfg(test)]
 mod tests {
@@ -252,12 +266,12 @@ noop";
 
     #[test]
     fn part1() {
-        assert_eq!(process_part1(INPUT), "13140");
+        assert_eq!(process_part1(INPUT).unwrap(), "13140");
     }
 
     #[test]
     fn part2() {
-       assert_eq!(process_part2(INPUT), "##..##..##..##..##..##..##..##..##..##..
+       assert_eq!(process_part2(INPUT).unwrap(), "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
 #####.....#####.....#####.....#####.....
@@ -1,100 +1,69 @@
+use aoclib::{math, simulation, Answer, ParseError, ParseResult, Simulation, Solution};
+use aoclib::parse::{expect, integer, labelled, literal, numbers, paragraphs, parse_all, IResult};
 use std::{collections::VecDeque, cmp::Ordering};
 
 use nom::{
-    IResult,
-    character::{
-        complete::{digit1, multispace0, line_ending, alpha1, alpha0}, 
-    },
-    sequence::{preceded, terminated, tuple},
-    bytes::complete::{tag, take_till, take_while}, 
-    multi::separated_list0, 
-    combinator::opt, 
+    character::complete::{line_ending, one_of, space0, space1}, 
+    sequence::{delimited, pair, preceded, tuple},
+    bytes::complete::tag, 
+    combinator::{consumed, map, value, verify}, 
     branch::alt, 
-    error::Error,
 };
 
 
-fn parse_monkey(input: &str) -> IResult<&str, &str> {
-        terminated(
-        preceded(
-            tuple((multispace0, tag("Monkey"), multispace0)), 
-            digit1
-        ),
-        tuple((tag(":"), opt(line_ending))),
-    )(input)
+fn parse_monkey(input: &str) -> IResult<'_, u32> {
+    delimited(literal("Monkey "), integer, literal(":"))(input)
 }
 
-fn parse_starting_items(input: &str) -> IResult<&str,Vec<&str>> {
-    terminated(
-    preceded(
-        take_till(|c: char| c.is_digit(10)), 
-        separated_list0(
-            tuple((tag(","), opt(multispace0))),
-            digit1,
-        )
-    ),
-    opt(line_ending),
+fn parse_starting_items(input: &str) -> IResult<'_, Vec<u64>> {
+    labelled("Starting items", numbers(literal(", ")))(input)
+}
+
+fn parse_operand(input: &str) -> IResult<'_, Operand> {
+    expect(
+        "`old` or number",
+        alt((
+            value(Operand::Old, tag("old")),
+            map(integer, Operand::Num),
+        )),
     )(input)
 }
 
-fn parse_operation(input: &str) -> IResult<&str, Operation> {
-    let (input, (operand1, operator, operand2)) = terminated(
+fn parse_operation(input: &str) -> IResult<'_, Operation> {
+    let (input, (operand1, operator, operand2)) = labelled(
+        "Operation",
         preceded(
-            tuple((multispace0, tag("Operation: new ="), multispace0)),
+            literal("new = "),
             tuple((
-                alt((tag("old"), digit1)),
-                preceded(
-                    opt(multispace0), 
-                    alt((tag("+"), tag("*"))),
-                ),
-                preceded(
-                    opt(multispace0), 
-                    alt((digit1, tag("old")))
-                ),
+                parse_operand,
+                delimited(space1, one_of("+*"), space1),
+                parse_operand,
             )),
         ),
-        opt(line_ending),
     )(input)?;
 
-    let o1 = match operand1 {
-        "old" => Operand::Old,
-        n => Operand::Num(n.parse().unwrap_or(0)),
-    };
-    let o2 = match operand2 {
-        "old" => Operand::Old,
-        n => Operand::Num(n.parse().unwrap_or(0)),
-    };
-
-    return match operator {
-        "+" => Ok((input, Operation::Add(o1, o2))),
-        "*" => Ok((input, Operation::Mul(o1, o2))),
-        _ => Err(nom::Err::Incomplete(nom::Needed::Unknown)),
+    match operator {
+        '+' => Ok((input, Operation::Add(operand1, operand2))),
+        _ => Ok((input, Operation::Mul(operand1, operand2))),
     }
 }
 
-fn parse_test(input: &str) -> IResult<&str, (&str, &str)> {
-    terminated(
-        preceded(
-            tuple((multispace0, tag("Test:"), multispace0)),
-            tuple((
-                alpha1, 
-                preceded(tuple((opt(multispace0), opt(alpha0), opt(multispace0))), digit1),
-            )),
-        ),
-        opt(line_ending),
-    )(input)
+fn parse_test(input: &str) -> IResult<'_, i32> {
+    labelled("Test", preceded(literal("divisible by "), expect("divisor above 0", verify(integer, |&d: &i32| d > 0))))(input)
 }
 
-fn parse_test_branch(input: &str) -> IResult<&str, &str> {
-    terminated(
-        preceded(
-            tuple((multispace0, tag("If "), alt((tag("true"), tag("false"))), tag(":"), multispace0, tag("throw to monkey"), multispace0)),
-            digit1
-        ),
-        opt(line_ending),
+fn parse_test_branch(input: &str) -> IResult<'_, u64> {
+    preceded(
+        tuple((literal("If "), expect("`true` or `false`", alt((tag("true"), tag("false")))), literal(": throw to monkey "))),
+        integer,
     )(input)
 }
 
+// line ending with the indentation of the next line
+fn next_line(input: &str) -> IResult<'_, ()> {
+    value((), pair(line_ending, space0))(input)
+}
+
 #[derive(Debug, Clone)]
 enum Operand {
     Old,
@@ -116,6 +85,21 @@ enum Operation {
     Mul(Operand, Operand),
 }
 
+impl Operation {
+    // worry level after the inspection, `None` when it does not fit in 64 bits
+    fn apply(&self, old: u64) -> Option<u64> {
+        let value = |operand: &Operand| match operand {
+            Operand::Old => old,
+            Operand::Num(num) => *num as u64,
+        };
+
+        match self {
+            Operation::Add(left, right) => value(left).checked_add(value(right)),
+            Operation::Mul(left, right) => value(left).checked_mul(value(right)),
+        }
+    }
+}
+
 impl PartialEq for Operation {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -138,7 +122,7 @@ struct MonekyBehaviour {
 }
 
 #[allow(unused)]
-fn print_monkeys(monkeys: &Vec<MonekyBehaviour>) {
+fn print_monkeys(monkeys: &[MonekyBehaviour]) {
     for monkey in monkeys.iter() {
         println!("Monkey {}: {:?}, inspected {:}", monkey.id, monkey.items, monkey.inspected)
     }
@@ -155,39 +139,62 @@ impl PartialEq for MonekyBehaviour {
     }
 }
 
-fn parse_single(input: &str) -> IResult<&str, MonekyBehaviour> {
-    let (input, _) = take_while::<_, _, Error<_>>(|c: char| !c.is_alphanumeric())(input)?;
-    let (input, monkey_id) = parse_monkey(input)?;
-    let (input, starting_items) = parse_starting_items(input)?;
-    let (input, operation) = parse_operation(input)?;
-    let (input, (_, test_operand)) = parse_test(input)?;
-    let (input, throw_to_when_true) = parse_test_branch(input)?;
-    let (input, throw_to_when_false) = parse_test_branch(input)?;
-
-    Ok((input, MonekyBehaviour { 
-        id: monkey_id.parse().unwrap(), 
-        items: starting_items.iter().map(|item| item.parse().unwrap()).collect(), 
-        operation: operation,
-        test: test_operand.parse().unwrap_or(0), 
-        true_receiver: throw_to_when_true.parse().unwrap_or(0), 
-        false_receiver: throw_to_when_false.parse().unwrap_or(0),
-        inspected: 0,
-    }))
+fn parse_single(input: &str) -> IResult<'_, MonekyBehaviour> {
+    map(
+        tuple((
+            parse_monkey,
+            preceded(next_line, parse_starting_items),
+            preceded(next_line, parse_operation),
+            preceded(next_line, parse_test),
+            preceded(next_line, parse_test_branch),
+            preceded(next_line, parse_test_branch),
+        )),
+
+        |(id, items, operation, test, true_receiver, false_receiver)| MonekyBehaviour {
+            id,
+            items: items.into(),
+            operation,
+            test,
+            true_receiver,
+            false_receiver,
+            inspected: 0,
+        }
+    )(input)
 } 
 
-fn parse_input(input: &str) -> IResult<&str, Vec<MonekyBehaviour>> {
-    let mut input = input;
-    let mut result: Vec<MonekyBehaviour> = vec![];
-    while let Ok((output, monkey)) = parse_single(input) {
-        result.push(monkey);
-        input = output;
+// trimmed `nth` line of a monkey's paragraph, from its text after `prefix`
+fn monkey_line<'a>(paragraph: &'a str, nth: usize, prefix: &str) -> &'a str {
+    let line = paragraph.lines().nth(nth).unwrap();
+    &line[line.find(prefix).unwrap() + prefix.len()..]
+}
+
+// monkeys with their paragraph of the input, which errors found later point into
+fn parse_monkeys(input: &str) -> ParseResult<Vec<(&str, MonekyBehaviour)>> {
+    let monkeys = parse_all(input, paragraphs(consumed(parse_single)))?;
+
+    if monkeys.len() < 2 {
+        return Err(ParseError::at(input, &input[input.len()..], "at least two monkeys"));
     }
 
-    return Ok((input, result));
+    for (paragraph, monkey) in monkeys.iter() {
+        for (nth, receiver) in [(4, monkey.true_receiver), (5, monkey.false_receiver)] {
+            if receiver >= monkeys.len() as u64 {
+                let token = monkey_line(paragraph, nth, "monkey ");
+                return Err(ParseError::at(input, token, format!("monkey below {}", monkeys.len())));
+            }
+        }
+    }
+
+    Ok(monkeys)
+}
+
+fn parse_input(input: &str) -> ParseResult<Vec<MonekyBehaviour>> {
+    Ok(parse_monkeys(input)?.into_iter().map(|(_, monkey)| monkey).collect())
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (_, mut monkeys) = parse_input(input).unwrap();
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let paragraphs = parse_monkeys(input)?;
+    let mut monkeys = paragraphs.iter().map(|(_, monkey)| monkey.clone()).collect::<Vec<_>>();
 
     for _round in 1..21 {
         for i in 0..monkeys.len() {
@@ -196,15 +203,10 @@ pub fn process_part1(input: &str) -> String {
             while let Some(item) = monkeys[i].items.pop_front() {
                 monkeys[i].inspected+=1;
                 // println!("Monkey {} inspect element {}", current_monkey.id, item);
-                let worry_level = match monkeys[i].operation {
-                    Operation::Add(Operand::Old, Operand::Num(interest)) |
-                    Operation::Add(Operand::Num(interest), Operand::Old) => item + interest as u64,
-                    Operation::Add(Operand::Old, Operand::Old) => item + item,
-                    Operation::Mul(Operand::Old, Operand::Num(interest)) |
-                    Operation::Mul(Operand::Num(interest), Operand::Old) => item * interest as u64,
-                    Operation::Mul(Operand::Old, Operand::Old) => item * item,
-                    _ => 0,
-                } / 3;
+                let worry_level = current_monkey.operation.apply(item).ok_or_else(|| {
+                    let operation = monkey_line(paragraphs[i].0, 2, "new = ");
+                    ParseError::at(input, operation, "operation keeping worry levels within 64 bits")
+                })? / 3;
                 
                 if worry_level % current_monkey.test as u64 == 0 {
                     monkeys[current_monkey.true_receiver as usize].items.push_back(worry_level);
@@ -225,48 +227,121 @@ pub fn process_part1(input: &str) -> String {
         else { Ordering::Greater } 
     });
 
-    return (inspected[0] * inspected[1]).to_string()
+    Ok((inspected[0] * inspected[1]).to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (_, mut monkeys) = parse_input(input).unwrap();
+// Items are thrown independently of each other, so every item is simulated on its own,
+// one step is one round. Worry levels are kept modulo the lcm of all tests.
+#[derive(Clone)]
+struct ItemSimulation<'a> {
+    monkeys: &'a [MonekyBehaviour],
+    mod_num: u64,
+    monkey: usize,
+    worry_level: u64,
+}
 
-    let mod_num = monkeys.iter().map(|monkey| monkey.test as u64).product::<u64>();
-    for _ in 1..10_001 {
-        for i in 0..monkeys.len() {
-            let current_monkey = monkeys[i].clone();
+impl ItemSimulation<'_> {
+    // monkeys inspecting the item during the round and where it ends up
+    fn round(&self, (mut monkey, mut worry_level): (usize, u64)) -> (Vec<usize>, (usize, u64)) {
+        let mut inspectors = vec![];
+        loop {
+            inspectors.push(monkey);
+
+            let current_monkey = &self.monkeys[monkey];
+            let item = worry_level;
+            worry_level = match current_monkey.operation {
+                Operation::Add(Operand::Old, Operand::Num(interest)) |
+                Operation::Add(Operand::Num(interest), Operand::Old) => item + interest as u64,
+                Operation::Add(Operand::Old, Operand::Old) => item + item,
+                Operation::Mul(Operand::Old, Operand::Num(interest)) |
+                Operation::Mul(Operand::Num(interest), Operand::Old) => item * interest as u64,
+                Operation::Mul(Operand::Old, Operand::Old) => item * item,
+                _ => 0,
+            } % self.mod_num;
+
+            let receiver = if worry_level % current_monkey.test as u64 == 0 {
+                current_monkey.true_receiver
+            } else {
+                current_monkey.false_receiver
+            } as usize;
+
+            // monkeys later in the order throw the item again in the same round
+            if receiver <= monkey {
+                return (inspectors, (receiver, worry_level));
+            }
+            monkey = receiver;
+        }
+    }
+}
 
-            while let Some(item) = monkeys[i].items.pop_front() {
-                monkeys[i].inspected+=1;
-                let worry_level = match monkeys[i].operation {
-                    Operation::Add(Operand::Old, Operand::Num(interest)) |
-                    Operation::Add(Operand::Num(interest), Operand::Old) => item%mod_num + interest as u64,
-                    Operation::Add(Operand::Old, Operand::Old) => item%mod_num + item%mod_num,
-                    Operation::Mul(Operand::Old, Operand::Num(interest)) |
-                    Operation::Mul(Operand::Num(interest), Operand::Old) => item%mod_num * interest as u64,
-                    Operation::Mul(Operand::Old, Operand::Old) => item%mod_num * item%mod_num,
-                    _ => 0,
-                };
-                
-                if worry_level % current_monkey.test as u64 == 0 {
-                    monkeys[current_monkey.true_receiver as usize].items.push_back(worry_level);
-                } else {
-                    monkeys[current_monkey.false_receiver as usize].items.push_back(worry_level);
-                }
+impl Simulation for ItemSimulation<'_> {
+    type State = (usize, u64);
+
+    fn step(&mut self) -> bool {
+        (self.monkey, self.worry_level) = self.round(self.state()).1;
+        true
+    }
+
+    fn state(&self) -> Self::State {
+        (self.monkey, self.worry_level)
+    }
+
+    fn render(&self) -> String {
+        format!("Monkey {}: {}", self.monkey, self.worry_level)
+    }
+}
+
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    const ROUNDS: u64 = 10_000;
+
+    let monkeys = parse_input(input)?;
+    let mod_num = monkeys.iter().map(|monkey| monkey.test as u64).fold(1, math::lcm);
+
+    let mut inspected = vec![0; monkeys.len()];
+    for (id, monkey) in monkeys.iter().enumerate() {
+        for &item in monkey.items.iter() {
+            let mut item_sim = ItemSimulation { monkeys: &monkeys, mod_num, monkey: id, worry_level: item % mod_num };
+            // items fall into short cycles, so rounds after the first cycle are not simulated
+            let history = simulation::record(&mut item_sim, ROUNDS as usize);
+
+            for (inspector, count) in inspected.iter_mut().enumerate() {
+                *count += history
+                    .sum_over(ROUNDS, |&state| {
+                        item_sim.round(state).0.iter().filter(|&&m| m == inspector).count() as u64
+                    })
+                    .unwrap_or(0);
             }
         }
     }
 
-    let mut inspected = monkeys.iter().map(|monkey| monkey.inspected).collect::<Vec<u64>>();
     inspected.sort_by(|v1, v2| {
         if v1 == v2 { Ordering::Equal }
         else if v1 > v2 { Ordering::Less }
         else { Ordering::Greater } 
     });
 
-    return (inspected[0] * inspected[1]).to_string()
+    Ok((inspected[0] * inspected[1]).to_string())
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 11;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
 }
 
+aoclib::expected_tests!(Day11);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -308,20 +383,45 @@ Monkey 3:
 
     #[test]
     fn part2() {
-        assert_eq!("2713310158", process_part2(INPUT));
+        assert_eq!("2713310158", process_part2(INPUT).unwrap());
     }
 
     #[test]
     fn part1() {
-        assert_eq!("10605", process_part1(INPUT));
+        assert_eq!("10605", process_part1(INPUT).unwrap());
     }
 
     #[test]
     fn parse_all(){
-        let (_, monkeys) = parse_input(INPUT).unwrap();
+        let monkeys = parse_input(INPUT).unwrap();
         assert_eq!(4, monkeys.len());
     }
 
+    #[test]
+    fn parse_invalid_operation() {
+        let err = parse_input(&INPUT.replace("old + 6", "old - 6")).unwrap_err();
+        assert_eq!((err.line, err.column), (10, 24));
+    }
+
+    #[test]
+    fn parse_invalid_monkeys() {
+        let err = parse_input(&INPUT.replace("throw to monkey 0", "throw to monkey 4")).unwrap_err();
+        assert_eq!(err.to_string(), "line 13, column 31: expected monkey below 4, found `4`");
+
+        let err = parse_input(&INPUT.replace("divisible by 19", "divisible by 0")).unwrap_err();
+        assert_eq!(err.to_string(), "line 11, column 22: expected divisor above 0, found `0`");
+
+        let err = parse_input(INPUT.split("\n\n").next().unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "line 6, column 32: expected at least two monkeys, found end of input");
+    }
+
+    #[test]
+    fn worry_level_overflow() {
+        let input = INPUT.replace("Starting items: 74", "Starting items: 18446744073709551615");
+        let err = process_part1(&input).unwrap_err();
+        assert_eq!(err.to_string(), "line 24, column 20: expected operation keeping worry levels within 64 bits, found `old + 3`");
+    }
+
     #[test]
     fn parse_sinble_into_struct() {
         assert_eq!(("", MonekyBehaviour{
@@ -338,29 +438,27 @@ Monkey 3:
     #[test]
     fn parse_single_separated() {
         let (input, monkey_id) = parse_monkey(INPUT_SINGLE).unwrap();
-        assert_eq!(input, "Starting items: 79, 98\nOperation: new = old * 19\nTest: divisible by 23\n    If true: throw to monkey 2\n    If false: throw to monkey 3");
-        assert_eq!(monkey_id, "0");
+        assert_eq!(input, "\nStarting items: 79, 98\nOperation: new = old * 19\nTest: divisible by 23\n    If true: throw to monkey 2\n    If false: throw to monkey 3");
+        assert_eq!(monkey_id, 0);
 
-        let (input, starting_items) = parse_starting_items(input).unwrap();
-        assert_eq!(input, "Operation: new = old * 19\nTest: divisible by 23\n    If true: throw to monkey 2\n    If false: throw to monkey 3");
-        assert_eq!(starting_items, vec!["79", "98"]);
+        let (input, starting_items) = preceded(next_line, parse_starting_items)(input).unwrap();
+        assert_eq!(input, "\nOperation: new = old * 19\nTest: divisible by 23\n    If true: throw to monkey 2\n    If false: throw to monkey 3");
+        assert_eq!(starting_items, vec![79, 98]);
 
-        let (input,operation) = parse_operation(input).unwrap();
-        assert_eq!(input, "Test: divisible by 23\n    If true: throw to monkey 2\n    If false: throw to monkey 3");
+        let (input,operation) = preceded(next_line, parse_operation)(input).unwrap();
+        assert_eq!(input, "\nTest: divisible by 23\n    If true: throw to monkey 2\n    If false: throw to monkey 3");
         assert_eq!(operation, Operation::Mul(Operand::Old, Operand::Num(19)));
 
-        let (input, (operator, operand)) = parse_test(input).unwrap();
-        assert_eq!(input, "    If true: throw to monkey 2\n    If false: throw to monkey 3");
-        assert_eq!(operator, "divisible");
-        assert_eq!(operand, "23");
+        let (input, operand) = preceded(next_line, parse_test)(input).unwrap();
+        assert_eq!(input, "\n    If true: throw to monkey 2\n    If false: throw to monkey 3");
+        assert_eq!(operand, 23);
 
-        let (input, throw_to) = parse_test_branch(input).unwrap();
-        assert_eq!(input, "    If false: throw to monkey 3");
-        assert_eq!(throw_to, "2");
+        let (input, throw_to) = preceded(next_line, parse_test_branch)(input).unwrap();
+        assert_eq!(input, "\n    If false: throw to monkey 3");
+        assert_eq!(throw_to, 2);
 
-        
-        let (input, throw_to) = parse_test_branch(input).unwrap();
+        let (input, throw_to) = preceded(next_line, parse_test_branch)(input).unwrap();
         assert_eq!(input, "");
-        assert_eq!(throw_to, "3");
+        assert_eq!(throw_to, 3);
     }
 }
\ No newline at end of file
@@ -1,36 +1,38 @@
+use aoclib::{parse::parse_token, Answer, ParseResult, Solution};
 
-pub fn parse_input(input: &str) -> (Vec<i32>, Vec<i32>) {
+
+pub fn parse_input(input: &str) -> ParseResult<(Vec<i32>, Vec<i32>)> {
     let mut first_list: Vec<i32> = Vec::new();
     let mut second_list: Vec<i32> = Vec::new();
 
     for (i, token) in input.split_ascii_whitespace().enumerate() {
         if i % 2 == 0 {
-            first_list.push(token.parse().unwrap());
+            first_list.push(parse_token(input, token, "location id")?);
         } else {
-            second_list.push(token.parse().unwrap());
+            second_list.push(parse_token(input, token, "location id")?);
         }
     }
 
     first_list.sort();
     second_list.sort();
 
-    (first_list, second_list)
+    Ok((first_list, second_list))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (first_list, second_list) = parse_input(input);
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let (first_list, second_list) = parse_input(input)?;
 
-    first_list
+    Ok(first_list
         .iter()
         .zip(second_list.iter())
         .fold(0, |sum,(&l, &r)| sum + (l-r).abs() )
-        .to_string()
+        .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (first_list, second_list) = parse_input(input);
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let (first_list, second_list) = parse_input(input)?;
 
-    first_list
+    Ok(first_list
         .iter()
         .map(|&left_id| {
             second_list
@@ -40,28 +42,25 @@ pub fn process_part2(input: &str) -> String {
                 .count() * left_id as usize
         })
         .sum::<usize>()
-        .to_string()
+        .to_string())
 }
 
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 1;
+
+    type Params = ();
 
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
 
-    
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day01);
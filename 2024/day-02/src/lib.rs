@@ -1,9 +1,11 @@
-fn process_input(input: &str) -> Vec<Vec<i32>> {
+use aoclib::{parse::parse_token, Answer, ParseResult, Solution};
+
+fn process_input(input: &str) -> ParseResult<Vec<Vec<i32>>> {
     input
-        .split("\n")
+        .lines()
         .map(|line| line
             .split(" ")
-            .map(|val| val.parse::<i32>().unwrap())
+            .map(|val| parse_token(input, val, "report level"))
             .collect()
         )
         .collect()
@@ -30,14 +32,14 @@ fn is_report_safe(report_line: &[i32]) -> bool {
         .all(|item| item.abs() > 0 && item.abs() < 4);
 
 
-    return all_safe_values && (all_decreasing || all_increasing)
+    all_safe_values && (all_decreasing || all_increasing)
 }
 
-fn is_single_tolerance_safe(report_line: &Vec<i32>) -> bool {
+fn is_single_tolerance_safe(report_line: &[i32]) -> bool {
     for i in 0..(report_line.len()) {
         let iter = report_line.iter().clone();
 
-        if is_report_safe( &iter.clone().take(i).chain(iter.skip(i+1)).map(|&v| v).collect::<Vec<i32>>()) {
+        if is_report_safe( &iter.clone().take(i).chain(iter.skip(i+1)).copied().collect::<Vec<i32>>()) {
             return true
         }
     }
@@ -45,40 +47,37 @@ fn is_single_tolerance_safe(report_line: &Vec<i32>) -> bool {
     false
 }
 
-pub fn process_part1(input: &str) -> String {
-    process_input(input)
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    Ok(process_input(input)?
         .iter()
         .filter(|&report_line| is_report_safe(report_line))
         .count()
-        .to_string()
+        .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    process_input(input)
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    Ok(process_input(input)?
         .iter()
         .filter(|&report_line| is_report_safe(report_line) || is_single_tolerance_safe(report_line))
         .count()
-        .to_string()    
+        .to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 2;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
 
-    
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day02);
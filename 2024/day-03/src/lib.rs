@@ -1,3 +1,4 @@
+use aoclib::{parse::parse_token, Answer, ParseResult, Solution};
 use regex::Regex;
 
 #[derive(Debug)]
@@ -8,7 +9,7 @@ enum Op {
     Mul(i32, i32),
 }
 
-fn parse_input(input: &str) -> Vec<Op> {
+fn parse_input(input: &str) -> ParseResult<Vec<Op>> {
     let re = Regex::new(
         r"(?<mnemonic>mul\((?<p1>\d+),(?<p2>\d+)\)|do\(\)|don't\(\))",
     ).unwrap();
@@ -16,19 +17,16 @@ fn parse_input(input: &str) -> Vec<Op> {
     re.captures_iter(input).map(|caps| {
         let mnemonic = caps.name("mnemonic").unwrap().as_str();
         if mnemonic.starts_with("don't") {
-            Op::DoNot
+            Ok(Op::DoNot)
         } else if mnemonic.starts_with("do") {
-            Op::Do
+            Ok(Op::Do)
         } else if mnemonic.starts_with("mul") {
-            caps.name("p1").unwrap().as_str();
-            caps.name("p2").unwrap().as_str();
-
-            Op::Mul(
-                caps.name("p1").unwrap().as_str().parse::<i32>().unwrap(),
-                caps.name("p2").unwrap().as_str().parse::<i32>().unwrap(),
-            )
+            Ok(Op::Mul(
+                parse_token(input, caps.name("p1").unwrap().as_str(), "mul operand")?,
+                parse_token(input, caps.name("p2").unwrap().as_str(), "mul operand")?,
+            ))
         } else {
-            Op::Unknown
+            Ok(Op::Unknown)
         }
     })
     .collect()
@@ -53,29 +51,31 @@ fn eval(program: Vec<Op>, with_disabled_memory: bool) -> i32 {
     sum
 }
 
-pub fn process_part1(input: &str) -> String {
-    let program = parse_input(input);
-    eval(program, false).to_string()
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let program = parse_input(input)?;
+    Ok(eval(program, false).to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let program = parse_input(input);
-    eval(program, true).to_string()
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let program = parse_input(input)?;
+    Ok(eval(program, true).to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT1: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-    const INPUT2: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 3;
+
+    type Params = ();
 
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT1));
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
 
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT2));
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day03);
@@ -1,49 +1,57 @@
+use aoclib::{parse::parse_token, Answer, ParseError, ParseResult, Solution};
 use std::collections::HashMap;
 
 type OrderingRules = HashMap<String, ()>;
 type UpdateLine<'a> = Vec<&'a str>;
 type Updates<'a> = Vec<UpdateLine<'a>>;
 
-fn parse_input(input: &str) -> (OrderingRules, Updates) {
-    let ordering_rules = input
+fn parse_input(input: &str) -> ParseResult<(OrderingRules, Updates<'_>)> {
+    let (rules_section, updates_section) = input.split_once("\n\n")
+        .ok_or_else(|| ParseError::at(input, &input[input.len()..], "empty line before updates"))?;
+
+    let ordering_rules = rules_section
         .lines()
-        .filter(|line| line.contains("|"))
-        .map(|line| (line.to_owned(), ()))
-        .collect::<OrderingRules>();
+        .map(|line| {
+            let (before, after) = line.split_once("|")
+                .ok_or_else(|| ParseError::at(input, line, "rule like `47|53`"))?;
+            parse_token::<i32>(input, before, "page number")?;
+            parse_token::<i32>(input, after, "page number")?;
+
+            Ok((line.to_owned(), ()))
+        })
+        .collect::<ParseResult<OrderingRules>>()?;
 
-        let updates: Updates = input
+    let updates: Updates = updates_section
         .lines()
-        .filter_map(
-            |val| {
-                let rule: Vec<&str> = val
-                    .split(",")
-                    .collect();
-
-                if rule.len() > 1 {
-                    Some(rule)
-                } else {
-                    None
-                }                
+        .map(|val| {
+            let rule: Vec<&str> = val
+                .split(",")
+                .collect();
+
+            for page in rule.iter() {
+                parse_token::<i32>(input, page, "page number")?;
             }
-        )
-        .collect();
 
+            Ok(rule)
+        })
+        .collect::<ParseResult<Updates>>()?;
 
-    (ordering_rules, updates)
+
+    Ok((ordering_rules, updates))
 }
 
 fn is_valid_update(rules: &OrderingRules, update: &UpdateLine) -> bool {
     update
         .windows(2)
         .all(|pages| rules.contains_key(
-            format!("{}|{}", pages.get(0).unwrap(), pages.get(1).unwrap()).as_str()
+            format!("{}|{}", pages.first().unwrap(), pages.get(1).unwrap()).as_str()
         ))
 }
 
-pub fn process_part1(input: &str) -> String {
-    let(rules, updates) =  parse_input(input);
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let(rules, updates) =  parse_input(input)?;
 
-    updates
+    Ok(updates
         .iter()
         .filter(|&update| is_valid_update(&rules, update))
         .map(|update| update
@@ -52,16 +60,16 @@ pub fn process_part1(input: &str) -> String {
             .unwrap()
         )
         .sum::<i32>()
-        .to_string()
+        .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let(rules, updates) =  parse_input(input);
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let(rules, updates) =  parse_input(input)?;
     let violate_rule = |p1: &str, p2: &str| {
         !rules.contains_key(format!("{}|{}", p1, p2).as_str())
     };
 
-    updates
+    Ok(updates
         .iter()
         .filter(|update| !is_valid_update(&rules, update))
         .map(|update| {
@@ -79,43 +87,24 @@ pub fn process_part2(input: &str) -> String {
                 .unwrap()
         })
         .sum::<i32>()
-        .to_string()
+        .to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-97,13,75,29,47";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 5;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
 
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
 }
+
+aoclib::expected_tests!(Day05);
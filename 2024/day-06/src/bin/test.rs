@@ -1,17 +0,0 @@
-use day_06::process_part2;
-
-
-const INPUT: &str = "....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
-fn main() {
-    
-    println!("{}", process_part2(INPUT));
-}
\ No newline at end of file
@@ -1,49 +1,43 @@
+use aoclib::{simulation, Answer, Direction, ParseError, ParseResult, Simulation, Solution, Vec2};
 use std::collections::HashMap;
 
 use itertools::Itertools;
 
-type Point = (i32, i32);
-
-enum SimulationResult {
-    Finished(i32),
-    Loop(i32),
-}
-
 #[derive(Clone)]
-struct GuardSimulator {
+pub struct GuardSimulator {
     board: Vec<char>,
     rows: usize,
     cols: usize,
 
-    guard_pos: Point,
+    guard_pos: Vec2,
     guard_direction: Direction,
 
-    visited: HashMap<Point, ()>,
-    steps: HashMap<(Point, Point), ()>,
-}
-
-#[derive(Clone)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+    visited: HashMap<Vec2, ()>,
 }
 
-fn pos2point(pos: usize, board_width: usize) -> Point {
+fn pos2point(pos: usize, board_width: usize) -> Vec2 {
     let y = pos / board_width;
     let x = pos % board_width;
 
-    (x as i32, y as i32)
+    (x as i32, y as i32).into()
 }
 
 
 impl GuardSimulator {
-    fn from_string<T>(input: T) -> Self
-        where T: Into<String> 
-    {
-        let map_str: String = input
-            .into();
+    pub fn parse(map_str: &str) -> ParseResult<Self> {
+        let cols = map_str
+            .lines()
+            .next()
+            .map_or(0, |line| line.len());
+
+        for line in map_str.lines() {
+            if let Some(pos) = line.find(|c| !".#^".contains(c)) {
+                return Err(ParseError::at(map_str, &line[pos..pos+1], "`.`, `#` or `^`"));
+            }
+            if line.len() != cols || cols == 0 {
+                return Err(ParseError::at(map_str, line, format!("row of {} cells", cols.max(1))));
+            }
+        }
 
         let board: Vec<char> = map_str
             .lines()
@@ -56,56 +50,37 @@ impl GuardSimulator {
             .lines()
             .count();
 
-        let cols = map_str
-            .lines()
-            .next()
-            .unwrap()
-            .len();
-
         let guard_pos = board
             .iter()
             .position(|&c| c == '^')
-            .unwrap();
+            .ok_or_else(|| ParseError::at(map_str, &map_str[map_str.len()..], "guard `^`"))?;
 
         let mut visited = HashMap::new();
         visited.insert(pos2point(guard_pos, cols), ());
 
-        return Self{
-            board: board,
-            rows: rows,
-            cols: cols,
+        Ok(Self{
+            board,
+            rows,
+            cols,
 
             guard_direction: Direction::Up,
             guard_pos: pos2point(guard_pos, cols),
-            steps: HashMap::new(),
-            visited: visited,
-        }
+            visited,
+        })
     }
 
-    fn step(&mut self) {
+    fn move_guard(&mut self) {
         if self.is_obstruction_in_front_of_guard() {
-            self.guard_direction = match self.guard_direction {
-                Direction::Up => Direction::Right,
-                Direction::Right => Direction::Down,
-                Direction::Down => Direction::Left,
-                Direction::Left => Direction::Up,
-            };
+            self.guard_direction = self.guard_direction.turn_right();
 
         } else {
-
-            self.steps.insert((self.guard_pos, self.next_guard_pos()), ());
             self.guard_pos = self.next_guard_pos();
             self.visited.insert(self.guard_pos, ());
         }
     }
 
-    fn next_guard_pos(&self) -> Point {
-        match self.guard_direction {
-            Direction::Up => (self.guard_pos.0, self.guard_pos.1-1),
-            Direction::Right => (self.guard_pos.0+1, self.guard_pos.1),
-            Direction::Down => (self.guard_pos.0, self.guard_pos.1+1),
-            Direction::Left => (self.guard_pos.0-1, self.guard_pos.1),
-        }
+    fn next_guard_pos(&self) -> Vec2 {
+        self.guard_pos + self.guard_direction.offset()
     }
 
     fn distinct_visited(&self) -> usize {
@@ -115,22 +90,22 @@ impl GuardSimulator {
     fn no_more_steps(&self) -> bool {
         let next_pos = self.next_guard_pos();
 
-        return next_pos.0 < 0 
-            || next_pos.1 < 0 
-            || next_pos.0 >= self.cols as i32 
-            || next_pos.1 >= self.rows as i32
+        next_pos.x < 0 
+            || next_pos.y < 0 
+            || next_pos.x >= self.cols as i32 
+            || next_pos.y >= self.rows as i32
     }
 
     fn is_obstruction_in_front_of_guard(&self) -> bool {
-        let front_point: Point = self.next_guard_pos();
-        return self.board.get(self.point2pos(front_point)).unwrap().to_owned() == '#'
+        let front_point = self.next_guard_pos();
+        *self.board.get(self.point2pos(front_point)).unwrap() == '#'
     }
 
-    fn point2pos(&self, p: Point) -> usize {
-        p.0 as usize + p.1 as usize *self.cols
+    fn point2pos(&self, p: Vec2) -> usize {
+        p.x as usize + p.y as usize *self.cols
     }
 
-    fn pos2point(&self, pos: usize) -> Point {
+    fn pos2point(&self, pos: usize) -> Vec2 {
         pos2point(pos, self.cols)
     }
 
@@ -149,35 +124,8 @@ impl GuardSimulator {
         self.board[pos] = '#'
     }
 
-    fn is_guard_in_loop(&self) -> bool {
-        let next_step = self.next_guard_pos();
-
-        self.steps.get(&(self.guard_pos, next_step)).is_some()
-    }
-
-    fn run_simulation(&mut self) -> SimulationResult {
-        loop {
-            if self.no_more_steps() {
-                break;
-            }
-            self.step();
-            
-            if self.is_guard_in_loop() {
-                return SimulationResult::Loop(self.distinct_visited() as i32);
-            }
-        }
-
-        SimulationResult::Finished(self.distinct_visited() as i32)
-    }
-
-    #[allow(unused)]
-    fn to_string(&self) -> String {
-        let dir_indicator = match self.guard_direction {
-            Direction::Up => '^',
-            Direction::Right => '>',
-            Direction::Down => 'v',
-            Direction::Left => '<',
-        };
+    fn visualization(&self) -> String {
+        let dir_indicator = self.guard_direction.as_char();
 
         self.board
             .iter()
@@ -187,7 +135,7 @@ impl GuardSimulator {
                     dir_indicator
                 } else if c == '^' {
                     '.'
-                } else if self.visited.get(&self.pos2point(pos)).is_some() {
+                } else if self.visited.contains_key(&self.pos2point(pos)) {
                     'X'
                 } else {
                     c
@@ -204,20 +152,37 @@ impl GuardSimulator {
     }
 }
 
+// walks until the guard leaves the lab, the guard is in a loop when the state repeats
+impl Simulation for GuardSimulator {
+    type State = (Vec2, Direction);
+
+    fn step(&mut self) -> bool {
+        if self.no_more_steps() {
+            return false;
+        }
+
+        self.move_guard();
+        true
+    }
+
+    fn state(&self) -> Self::State {
+        (self.guard_pos, self.guard_direction)
+    }
 
-pub fn process_part1(input: &str) -> String {
-    let mut sim = GuardSimulator::from_string(input);
+    fn render(&self) -> String {
+        self.visualization()
+    }
+}
 
-    let visited_fields = match sim.run_simulation() {
-        SimulationResult::Finished(v) => {v}
-        SimulationResult::Loop(v) => {v}
-    };
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let mut sim = GuardSimulator::parse(input)?;
+    while sim.step() {}
 
-   visited_fields.to_string()
+   Ok(sim.distinct_visited().to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let sim = GuardSimulator::from_string(input);
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let sim = GuardSimulator::parse(input)?;
     let mut possible_loops = 0;
 
     for i in 0..sim.board.len()-1 {
@@ -228,37 +193,29 @@ pub fn process_part2(input: &str) -> String {
 
         sim_cpy.put_obstruction(i);
 
-        match sim_cpy.run_simulation() {
-            SimulationResult::Finished(_) => {}
-            SimulationResult::Loop(_) => {possible_loops += 1}
+        if simulation::find_cycle(&sim_cpy).is_some() {
+            possible_loops += 1;
         }
     }
 
-    format!("{}", possible_loops)
+    Ok(format!("{}", possible_loops))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+pub struct Day06;
+
+impl Solution for Day06 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 6;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
 
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
 }
 
+aoclib::expected_tests!(Day06);
@@ -1,8 +1,10 @@
+use aoclib::{Answer, ParseError, ParseResult, Solution};
 use nom::{
     bytes::complete::tag, 
-    character::complete::{self, line_ending, space1}, 
+    character::complete::{self, line_ending, multispace0, space1}, 
+    combinator::all_consuming,
     multi::separated_list1, 
-    sequence::separated_pair, IResult
+    sequence::{separated_pair, terminated}, IResult
 };
 
 struct Calibration {
@@ -49,8 +51,8 @@ impl Calibration {
 }
 
 
-fn parse_input(input: &str) -> Vec<Calibration>{
-    let parser_result: IResult<&str, Vec<(u128, Vec<u64>)>>  = separated_list1(
+fn parse_input(input: &str) -> ParseResult<Vec<Calibration>> {
+    let parser_result: IResult<&str, Vec<(u128, Vec<u64>)>>  = all_consuming(terminated(separated_list1(
         line_ending, 
         separated_pair(
             complete::u128, 
@@ -60,57 +62,52 @@ fn parse_input(input: &str) -> Vec<Calibration>{
                 complete::u64
             )
         )
-    )(input);
+    ), multispace0))(input);
 
-    let (_, equations) = parser_result.unwrap(); 
+    let (_, equations) = parser_result.map_err(|err| ParseError::from_nom(input, err))?; 
 
-    equations
+    Ok(equations
         .iter()
         .map(|(res, params)| Calibration{
             result: *res,
             params: params.clone(),
         })
-        .collect()
+        .collect())
 }
 
-pub fn process_part1(input: &str) -> String {
-    parse_input(input)    
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    Ok(parse_input(input)?
         .iter()
         .filter(|calibration| calibration.is_valid(false))
         .map(|calibration| calibration.result)
         .sum::<u128>()
-        .to_string()
+        .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    parse_input(input)    
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    Ok(parse_input(input)?
         .iter()
         .filter(|calibration| calibration.is_valid(true))
         .map(|calibration| calibration.result)
         .sum::<u128>()
-        .to_string()
+        .to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+pub struct Day07;
+
+impl Solution for Day07 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 7;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
 
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day07);
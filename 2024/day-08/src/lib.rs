@@ -1,4 +1,5 @@
-use std::{collections::{HashMap, HashSet}, convert::From};
+use aoclib::{Answer, ParseError, ParseResult, Solution};
+use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
@@ -14,30 +15,29 @@ struct FrequencyMap {
 }
 
 impl FrequencyMap {
-    fn from_string<T>(input: T) -> FrequencyMap 
-        where T: Into<String> {
-
-        let input: String = input.into();
+    fn parse(input: &str) -> ParseResult<FrequencyMap> {
+        let width = input.lines().next().map_or(0, |row| row.len());
+
+        for row in input.lines() {
+            if let Some(pos) = row.find(|c: char| c != '.' && !c.is_ascii_alphanumeric()) {
+                return Err(ParseError::at(input, &row[pos..pos+1], "`.` or antenna frequency"));
+            }
+            if row.len() != width || width == 0 {
+                return Err(ParseError::at(input, row, format!("row of {} cells", width.max(1))));
+            }
+        }
 
         let board = input
             .lines()
-            .map(|row| row.chars().collect::<Vec<char>>())
-            .flatten()
+            .flat_map(|row| row.chars().collect::<Vec<char>>())
             .collect::<Vec<char>>();
     
-    
-        let width = input.lines().next().unwrap().len();
-    
-        FrequencyMap{
-            board: board,
+        Ok(FrequencyMap{
+            board,
             board_width: width,
             frequencies: HashMap::new(),
             antinodes: HashSet::new(),
-        }
-    }
-
-    fn point2pos(&self, p: Point) -> usize {
-        p.0 as usize + p.1 as usize *self.board_width
+        })
     }
 
     fn pos2point(&self, pos: usize) -> Point {
@@ -101,23 +101,22 @@ impl FrequencyMap {
                     .iter()
                     .cartesian_product(positions.iter())
                     .filter(|&(&p1, &p2)| p1 != p2)
-                    .map(|(p1, p2)| {
+                    .flat_map(|(p1, p2)| {
                         let vec2 = (p1.0-p2.0, p1.1-p2.1);
                         let mut res = vec![
-                            p1.clone(),
-                            p2.clone(),
+                            *p1,
+                            *p2,
                         ];
 
                         let mut point = (p1.0 + vec2.0, p1.1 + vec2.1);
                         while self.is_point_in_boundaries(point) {
-                            res.push(point.clone());
+                            res.push(point);
 
                             point = (point.0 + vec2.0, point.1 + vec2.1);
                         }
 
                         res
                     })
-                    .flatten()
                     .merge(acc)
                     .collect()
             });
@@ -125,15 +124,15 @@ impl FrequencyMap {
 
     fn count_unique_antinodes(&self) -> usize {
         self
-            .antinodes
-            .iter()
-            .count()
+            .antinodes.len()
     }
 }
 
-impl From<&str> for FrequencyMap {
-    fn from(input: &str) -> Self {
-        Self::from_string(input)
+impl TryFrom<&str> for FrequencyMap {
+    type Error = ParseError;
+
+    fn try_from(input: &str) -> ParseResult<Self> {
+        Self::parse(input)
     }
 }
 
@@ -170,57 +169,37 @@ impl std::fmt::Display for FrequencyMap {
     }
 }
 
-pub fn process_part1(input: &str) -> String {
-    let mut map: FrequencyMap = input.into();
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let mut map: FrequencyMap = input.try_into()?;
     map.compute_frequencies();
     map.compute_antinodes();
-    format!("{}", map.count_unique_antinodes())
+    Ok(format!("{}", map.count_unique_antinodes()))
 }
 
-pub fn process_part2(input: &str) -> String {
-    let mut map: FrequencyMap = input.into();
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let mut map: FrequencyMap = input.try_into()?;
     map.compute_frequencies();
     map.compute_resonant_harmonics_antinodes();
 
-    format!("{}", map.count_unique_antinodes())
+    Ok(format!("{}", map.count_unique_antinodes()))
 }
 
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............";
-
-    const INPUT2: &str = "T.........
-...T......
-.T........
-..........
-..........
-..........
-..........
-..........
-..........
-..........";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+pub struct Day08;
+
+impl Solution for Day08 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 8;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
 
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT2));
-        println!("{}", process_part2(INPUT));
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day08);
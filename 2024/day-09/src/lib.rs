@@ -1,3 +1,4 @@
+use aoclib::{Answer, ParseError, ParseResult, Solution};
 use std::collections::VecDeque;
 
 #[derive(PartialEq, Debug, Clone)]
@@ -7,17 +8,23 @@ enum FileSystemNode {
     Free,
 }
 
-fn parse_input(input: &str) -> Vec<FileSystemNode> {
-    input
-        .trim()
-        .chars()
-        .map(|c| {
-            c.to_digit(10).unwrap()
+fn parse_disk_map(input: &str) -> ParseResult<Vec<u32>> {
+    let disk_map = input.trim();
+
+    disk_map
+        .char_indices()
+        .map(|(pos, c)| {
+            c.to_digit(10)
+                .ok_or_else(|| ParseError::at(input, &disk_map[pos..pos+c.len_utf8()], "block size digit"))
         })
-        .collect::<Vec<u32>>()
+        .collect()
+}
+
+fn parse_input(input: &str) -> ParseResult<Vec<FileSystemNode>> {
+    Ok(parse_disk_map(input)?
         .chunks(2)
         .enumerate()
-        .map(|(idx, block_info)| {
+        .flat_map(|(idx, block_info)| {
             match block_info.len() {
                 0 => vec![],
                 1 => vec![
@@ -30,12 +37,11 @@ fn parse_input(input: &str) -> Vec<FileSystemNode> {
             }
         })
         .flatten()
-        .flatten()
-        .collect()
+        .collect())
 }
 
-pub fn process_part1(input: &str) -> String {
-    let disk = parse_input(input);
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let disk = parse_input(input)?;
     let mut files_on_disk: VecDeque<&FileSystemNode> = disk
         .iter()
         .filter(|block| !matches!(block, FileSystemNode::Free))
@@ -59,10 +65,10 @@ pub fn process_part1(input: &str) -> String {
         })
         .iter()
         .take(disk.len() - (all_files_count - files_on_disk.len()))
-        .map(|n| *n)
+        .copied()
         .collect();
 
-    organized_disk
+    Ok(organized_disk
         .iter()
         .enumerate()
         .map(|(pos, &fs_node)| {    
@@ -74,7 +80,7 @@ pub fn process_part1(input: &str) -> String {
             }
         })
         .sum::<u128>()
-        .to_string()
+        .to_string())
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -97,53 +103,46 @@ impl FsBlock {
         }
 
         (0..self.size)
-            .fold(0 as u128, |acc, cur_idx| {
+            .fold(0_u128, |acc, cur_idx| {
                 acc + self.val as u128 * (idx + cur_idx as u128)
             })
     }
 }
 
-impl Into<String> for FsBlock {
-    fn into(self) -> String {
-        match self.kind {
+impl From<FsBlock> for String {
+    fn from(block: FsBlock) -> Self {
+        match block.kind {
             FsBlockKind::File => {
-                vec![self.val.to_string(); self.size].iter().map(|x| x.as_str()).collect()
+                vec![block.val.to_string(); block.size].iter().map(|x| x.as_str()).collect()
             },
             FsBlockKind::Free => {
-                vec![".".to_owned(); self.size].iter().map(|x| x.as_str()).collect()
+                vec![".".to_owned(); block.size].iter().map(|x| x.as_str()).collect()
             }
         }
     }
 }
 
-fn parse_input2(input: &str) -> Vec<FsBlock> {
-    input
-        .trim()
-        .chars()
-        .map(|c| {
-            c.to_digit(10).unwrap()
-        })
-        .collect::<Vec<u32>>()
+fn parse_input2(input: &str) -> ParseResult<Vec<FsBlock>> {
+    Ok(parse_disk_map(input)?
         .chunks(2)
         .enumerate()
-        .map(|(idx, block_info)| {
+        .flat_map(|(idx, block_info)| {
             match block_info.len() {
                 0 => vec![],
                 1 => vec![
-                        FsBlock { val: idx, size: block_info.get(0).unwrap().to_owned() as usize, kind: FsBlockKind::File }
+                        FsBlock { val: idx, size: block_info.first().unwrap().to_owned() as usize, kind: FsBlockKind::File }
                     ],
                 _ => vec![
-                        FsBlock { val: idx, size: block_info.get(0).unwrap().to_owned() as usize, kind: FsBlockKind::File },
+                        FsBlock { val: idx, size: block_info.first().unwrap().to_owned() as usize, kind: FsBlockKind::File },
                         FsBlock { val: idx, size: block_info.get(1).unwrap().to_owned() as usize, kind: FsBlockKind::Free },
                     ]
             }
         })
-        .flatten()
-        .collect()
+        .collect())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let disk = parse_input2(input);
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let disk = parse_input2(input)?;
 
     let mut result = disk.clone();
 
@@ -155,7 +154,7 @@ pub fn process_part2(input: &str) -> String {
                 }
             }
         }
-        return None
+        None
     };
     
     let move_file = |fs_snapshot: &mut Vec<FsBlock>, from: usize, to: usize| {
@@ -184,13 +183,13 @@ pub fn process_part2(input: &str) -> String {
         .rev()
         .for_each(|fs_block| {
             match fs_block.kind {
-                FsBlockKind::Free => { return },
+                FsBlockKind::Free => { },
                 FsBlockKind::File => {
                     let free_slot = find_space_for_file(&result, fs_block.size, fs_block.val);
-                    let real_pos_from = find_pos_for(&result, &fs_block);
+                    let real_pos_from = find_pos_for(&result, fs_block);
 
-                    if free_slot.is_some() {
-                        move_file(&mut result, real_pos_from, free_slot.unwrap());
+                    if let Some(free_slot) = free_slot {
+                        move_file(&mut result, real_pos_from, free_slot);
                     }
                 }
             }
@@ -205,21 +204,24 @@ pub fn process_part2(input: &str) -> String {
         current_fs_size += block.size as u128;
     }
 
-    result_checksum.to_string()
+    Ok(result_checksum.to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "2333133121414131402";
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 9;
 
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
 
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day09);
@@ -1,13 +1,19 @@
+use aoclib::{Answer, ParseError, ParseResult, Solution};
 use std::collections::HashSet;
 
-fn parse_input(input: &str) -> Vec<Vec<i32>> {
+fn parse_input(input: &str) -> ParseResult<Vec<Vec<i32>>> {
     input
         .trim()
         .lines()
         .map(|line| {
             line
-                .chars()
-                .map(|c| c.to_digit(10).unwrap_or(99) as i32)
+                .char_indices()
+                .map(|(pos, c)| match c {
+                    '.' => Ok(99),
+                    _ => c.to_digit(10)
+                        .map(|height| height as i32)
+                        .ok_or_else(|| ParseError::at(input, &line[pos..pos+c.len_utf8()], "height digit or `.`")),
+                })
                 .collect()
         })
         .collect()
@@ -24,8 +30,8 @@ fn neighbors(point: Point) -> Vec<Point> {
     ]
 }
 
-fn find_path(map: &Vec<Vec<i32>>, points: &Vec<Point>) -> Vec<Point> {
-    if points.len() < 1{
+fn find_path(map: &Vec<Vec<i32>>, points: &[Point]) -> Vec<Point> {
+    if points.is_empty(){
         return vec![]
     }
 
@@ -34,7 +40,7 @@ fn find_path(map: &Vec<Vec<i32>>, points: &Vec<Point>) -> Vec<Point> {
 
     let next_points: Vec<Point> = points
         .iter()
-        .map(|&cur_point| {
+        .flat_map(|&cur_point| {
             let cur_height = map
                 .get(cur_point.1 as usize).unwrap()
                 .get(cur_point.0 as usize).unwrap().to_owned();
@@ -51,10 +57,9 @@ fn find_path(map: &Vec<Vec<i32>>, points: &Vec<Point>) -> Vec<Point> {
 
                     p_height == cur_height + 1
                 })
-                .map(|p| *p)
+                .copied()
                 .collect::<Vec<Point>>()
         })
-        .flatten()
         .collect();
 
     let next_step_res = find_path(map, &next_points);
@@ -62,15 +67,14 @@ fn find_path(map: &Vec<Vec<i32>>, points: &Vec<Point>) -> Vec<Point> {
     next_points
         .iter()
         .filter(|p| { 
-            map
+            *map
                 .get(p.1 as usize)
                 .unwrap()
                 .get(p.0 as usize)
-                .unwrap()
-                .to_owned() == 9
+                .unwrap() == 9
         })
         .chain(next_step_res.iter())
-        .map(|p| *p)
+        .copied()
         .collect()
 }
 
@@ -81,63 +85,58 @@ fn pos2point(pos: usize, width: usize) -> Point {
     (x as i32, y as i32)
 }
 
-pub fn process_part1(input: &str) -> String {
-    let map = parse_input(input);
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let map = parse_input(input)?;
 
-    input
+    Ok(input
         .lines()
         .collect::<String>()
         .match_indices("0")
         .map(|(i, _)| {
-            let start_point = pos2point(i, map.get(0).unwrap().len());
-            find_path(&map, &vec![start_point])
+            let start_point = pos2point(i, map.first().unwrap().len());
+            find_path(&map, &[start_point])
                 .iter()
-                .map(|p|*p)
+                .copied()
                 .collect::<HashSet<Point>>()
-                .iter()
-                .count()
+                .len()
         })
         .sum::<usize>()
-        .to_string()
+        .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
+pub fn process_part2(input: &str) -> ParseResult<String> {
    
-    let map = parse_input(input);
+    let map = parse_input(input)?;
 
-    input
+    Ok(input
         .lines()
         .collect::<String>()
         .match_indices("0")
         .map(|(i, _)| {
-            let start_point = pos2point(i, map.get(0).unwrap().len());
-            find_path(&map, &vec![start_point])
+            let start_point = pos2point(i, map.first().unwrap().len());
+            find_path(&map, &[start_point])
                 .len()
         })
         .sum::<usize>()
-        .to_string()
+        .to_string())
 }
 
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "89010123
-78121874
-87430965
-96549874
-45678903
-32019012
-01329801
-10456732";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 10;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
 
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day10);
@@ -1,11 +1,10 @@
-use std::collections::HashMap;
+use aoclib::{parse::parse_token, Answer, Memo, ParseResult, Solution};
 
 
-fn parse_input(input: &str) -> HashMap<u128, usize> {
+fn parse_input(input: &str) -> ParseResult<Vec<u128>> {
     input
-        .trim()
         .split_whitespace()
-        .map(|stone| (stone.parse::<u128>().unwrap(), 1))
+        .map(|stone| parse_token(input, stone, "stone number"))
         .collect()
 }
 
@@ -18,90 +17,86 @@ fn count_digits(num: u128) -> usize {
     let mut num = num;
     let mut c = 0;
     while num != 0 {
-        num = num/10;
+        num /= 10;
         c+=1;
     }
     
-    return c
+    c
 }
 
 
 fn split_stone(val: u128) -> Option<Vec<u128>> {
     let stone_digits = count_digits(val);
-    if  stone_digits % 2 != 0 {
+    if  !stone_digits.is_multiple_of(2) {
         None
     } else {
         let mut higher_half = val;
         for _ in 0..stone_digits/2 {
-            higher_half = higher_half / 10;
+            higher_half /= 10;
         }
 
         Some(vec![
             higher_half,
-            val-(higher_half*(10 as u128).pow(stone_digits as u32/2))
+            val-(higher_half*10_u128.pow(stone_digits as u32/2))
         ])
     }
 }
 
 
-pub fn blink(numbers: &HashMap<u128, usize>, blinks: usize) -> HashMap<u128, usize> {
-    let mut numbers = numbers.clone();
-    (0..blinks).for_each(|_| {
-        numbers = numbers
-            .iter()
-            .fold(HashMap::new(), |mut res: HashMap<u128, usize>, (&stone, count)| {
-  
-                if stone == 0 {
-                    *res.entry(1).or_default() += count;
-                } else if let Some(two_stones) = split_stone(stone) {
-                    *res.entry(two_stones.get(0).unwrap().to_owned()).or_default() += count;
-                    *res.entry(two_stones.get(1).unwrap().to_owned()).or_default() += count;
-                } else {
-                    *res.entry(stone*2024).or_default() += count;    
-                }
-
-                res
-            })
-    });
-
-    numbers
+// number of stones a single stone turns into after `blinks` blinks,
+// `count` is the memoised function itself
+fn count_stones(count: &mut dyn FnMut((u128, usize)) -> usize, (stone, blinks): (u128, usize)) -> usize {
+    if blinks == 0 {
+        return 1;
+    }
+
+    if stone == 0 {
+        count((1, blinks - 1))
+    } else if let Some(two_stones) = split_stone(stone) {
+        two_stones
+            .into_iter()
+            .map(|stone| count((stone, blinks - 1)))
+            .sum()
+    } else {
+        count((stone*2024, blinks - 1))
+    }
 }
 
-pub fn process_part1(input: &str) -> String {
-    let initial_stones = parse_input(input);
-    blink(&initial_stones, 25)
+pub fn blink(stones: &[u128], blinks: usize) -> usize {
+    let mut count = Memo::new().wrap(count_stones);
+
+    stones
         .iter()
-        .map(|(_, num)| {
-            *num
-        })
-        .sum::<usize>()
-        .to_string()
+        .map(|&stone| count.call((stone, blinks)))
+        .sum()
 }
 
-pub fn process_part2(input: &str) -> String {
-    let initial_stones = parse_input(input);
-    blink(&initial_stones, 75)
-        .iter()
-        .map(|(_, num)| {
-            *num
-        })
-        .sum::<usize>()
-        .to_string()
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let initial_stones = parse_input(input)?;
+    Ok(blink(&initial_stones, 25).to_string())
 }
 
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let initial_stones = parse_input(input)?;
+    Ok(blink(&initial_stones, 75).to_string())
+}
+
+
+pub struct Day11;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "125 17";
+impl Solution for Day11 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 11;
 
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
 
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day11);
@@ -1,9 +0,0 @@
-use std::fs;
-
-use day_12::process_part2;
-
-
-fn main() {
-    let input = fs::read_to_string("input.txt").unwrap();
-    println!("{}", process_part2(&input));
-}
\ No newline at end of file
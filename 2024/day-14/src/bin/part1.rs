@@ -1,9 +0,0 @@
-use std::fs;
-
-use day_14::process_part1;
-
-
-fn main() {
-    let input = fs::read_to_string("input.txt").unwrap();
-    println!("{}", process_part1(&input, 101, 103));
-}
\ No newline at end of file
@@ -1,19 +1,14 @@
-#[macro_use]
-extern crate bmp;
-
-use std::collections::HashSet;
+use aoclib::{math, simulation, Answer, Grid, ParseError, ParseResult, Simulation, Solution, Topology, Vec2};
+use aoclib::parse::{labelled, lines, parse_all, vec2, IResult};
+use serde::Deserialize;
 
 use nom::{
-    bytes::complete::tag, 
-    character::complete::{digit1, line_ending}, 
-    combinator::{map, opt, recognize},
-    sequence::{preceded, separated_pair, terminated, tuple}, 
-    IResult
+    character::complete::space1, 
+    combinator::{consumed, map},
+    sequence::separated_pair, 
 };
 
-type Vec2 = (i32, i32);
-
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Robot {
     pos: Vec2,
     vel: Vec2,
@@ -32,8 +27,8 @@ impl Robot {
         let height_medium = board_height / 2;
 
 
-        let px = self.pos.0;
-        let py  = self.pos.1;
+        let px = self.pos.x;
+        let py  = self.pos.y;
 
 
         if px < with_medium && py < height_medium {
@@ -49,89 +44,101 @@ impl Robot {
         }
     }
 
-    fn walk(&mut self, board_width: i32, board_height: i32) {
-        self.pos = (
-            (self.pos.0 + self.vel.0).rem_euclid(board_width),
-            (self.pos.1 + self.vel.1).rem_euclid(board_height),
-        );
+    fn walk(&mut self, board: &Grid<usize>) {
+        self.pos = board.wrap_point(self.pos + self.vel);
     }
 }
 
-fn robot_parser(input: &str) -> IResult<&str, Robot> {  
-    terminated(
-        map(
-            tuple((
-                preceded(
-                    tag("p="),
-                    separated_pair(
-                        recognize(preceded(opt(tag("-")), digit1)), 
-                        tag(","), 
-                        recognize(preceded(opt(tag("-")), digit1))
-                    ),
-                ),
-                preceded(
-                    tag(" v="), 
-                    separated_pair(
-                        recognize(preceded(opt(tag("-")), digit1)), 
-                        tag(","), 
-                        recognize(preceded(opt(tag("-")), digit1))
-                    )),
-                )
-            ),
-
-            |((px, py), (vx, vy)): ((&str, &str), (&str, &str))| {
-                Robot{
-                    pos: (
-                        px.parse::<i32>().unwrap(),
-                        py.parse::<i32>().unwrap(),
-                    ),
-                    vel: (
-                        vx.parse::<i32>().unwrap(),
-                        vy.parse::<i32>().unwrap(),
-                    ),
-                }
-            }
-        ),
-        opt(line_ending)
+fn robot_parser(input: &str) -> IResult<'_, Robot> {  
+    map(
+        separated_pair(labelled("p", vec2), space1, labelled("v", vec2)),
+        |(pos, vel)| Robot{ pos, vel }
     )(input)
 }
 
 
-fn parse_input(input: &str) -> Vec<Robot> {
-    let mut input = input;
-    let mut res = vec![];
-    while input.len() > 0 {
-        match robot_parser(input) {
-            Ok((rest, robot)) => {
-                input = rest;
-                res.push(robot);
-            },
-            Err(e) => {
-                panic!("{:?}", e);
-            },
+fn parse_input(input: &str, width: i32, height: i32) -> ParseResult<Vec<Robot>> {
+    parse_all(input, lines(consumed(robot_parser)))?
+        .into_iter()
+        .map(|(line, robot)| {
+            if (0..width).contains(&robot.pos.x) && (0..height).contains(&robot.pos.y) {
+                Ok(robot)
+            } else {
+                Err(ParseError::at(input, line, format!("robot on the {}x{} board", width, height)))
+            }
+        })
+        .collect()
+}
+
+// robots leaving the board through an edge appear on the opposite one,
+// parse_input keeps every robot on the board
+fn robots_board(robots: &[Robot], width: i32, height: i32) -> Grid<usize> {
+    let mut counts = vec![0; (width * height) as usize];
+    robots
+        .iter()
+        .for_each(|r| counts[(r.pos.x + r.pos.y * width) as usize] += 1);
+
+    Grid::new_from_raw(counts, width).with_topology(Topology::TorusDiagonal)
+}
+
+/// Robots walking around the bathroom, one second per step.
+#[derive(Clone)]
+pub struct RobotsSimulation {
+    robots: Vec<Robot>,
+    board: Grid<usize>,
+}
+
+impl RobotsSimulation {
+    pub fn parse(input: &str, params: &BoardParams) -> ParseResult<Self> {
+        let robots = parse_input(input, params.board_width, params.board_height)?;
+        let board = robots_board(&robots, params.board_width, params.board_height);
+
+        Ok(RobotsSimulation { robots, board })
+    }
+}
+
+impl Simulation for RobotsSimulation {
+    // velocities never change
+    type State = Vec<Vec2>;
+
+    fn step(&mut self) -> bool {
+        // the board is reused, only the counts of the cells robots leave and enter change
+        for robot in self.robots.iter_mut() {
+            let left = self.board.get_at(&robot.pos).copied().unwrap_or(0);
+            self.board.put_at(robot.pos, left.saturating_sub(1));
+
+            robot.walk(&self.board);
+            let entered = self.board.get_at(&robot.pos).copied().unwrap_or(0);
+            self.board.put_at(robot.pos, entered + 1);
         }
+
+        true
     }
 
-    res
+    fn state(&self) -> Self::State {
+        self.robots.iter().map(|robot| robot.pos).collect()
+    }
+
+    fn render(&self) -> String {
+        self.board.visualization(|&robots| {
+            match robots {
+                0 => '.',
+                n => char::from_digit(n.min(9) as u32, 10).unwrap(),
+            }
+        })
+    }
 }
 
-pub fn process_part1(input: &str, board_width: i32, board_height: i32) -> String {
-    let mut robots: Vec<Robot> = parse_input(input);
-    
-    (0..100).for_each(|_| {
-        robots
-            .iter_mut()
-            .for_each(|robot| {
-                robot.walk(board_width, board_height);
-            });
-    });
+pub fn process_part1(input: &str, board_width: i32, board_height: i32) -> ParseResult<String> {
+    let params = BoardParams { board_width, board_height };
+    let mut simulation = RobotsSimulation::parse(input, &params)?;
+    simulation::fast_forward(&mut simulation, 100);
 
-    
-    let quadrants_count = count_robots(&robots, board_width, board_height);
-    (quadrants_count.0 * quadrants_count.1 * quadrants_count.2 * quadrants_count.3).to_string()
+    let quadrants_count = count_robots(&simulation.robots, board_width, board_height);
+    Ok((quadrants_count.0 * quadrants_count.1 * quadrants_count.2 * quadrants_count.3).to_string())
 }
 
-fn count_robots(robots: &Vec<Robot>, board_width: i32, board_height: i32) -> (usize, usize, usize, usize) {
+fn count_robots(robots: &[Robot], board_width: i32, board_height: i32) -> (usize, usize, usize, usize) {
     use Quadrant::*;
 
     robots
@@ -149,40 +156,47 @@ fn count_robots(robots: &Vec<Robot>, board_width: i32, board_height: i32) -> (us
         })
 }
 
-pub fn process_part2(input: &str, board_width: i32, board_height: i32) -> String {
-    let mut robots: Vec<Robot> = parse_input(input);
-    
-    let mut result = 0;
-    (0..7711).for_each(|i| {
-        if result > 0 {
-            return
+pub fn process_part2(input: &str, board_width: i32, board_height: i32) -> ParseResult<String> {
+    let params = BoardParams { board_width, board_height };
+
+    Ok(find_tree(input, &params)?.map_or(0, |(second, _)| second).to_string())
+}
+
+/// Second at which the robots draw the christmas tree, with the board at that second.
+pub fn find_tree(input: &str, params: &BoardParams) -> ParseResult<Option<(usize, Grid<usize>)>> {
+    let mut simulation = RobotsSimulation::parse(input, params)?;
+
+    if let Some(second) = tree_candidate(&simulation.robots, params.board_width, params.board_height) {
+        let mut candidate = simulation.clone();
+        simulation::fast_forward(&mut candidate, second as u64);
+        if potential_tree(&candidate.board, candidate.robots.len()) {
+            return Ok(Some((second, candidate.board)));
         }
+    }
 
-        robots
-            .iter_mut()
-            .for_each(|robot| {
-                robot.walk(board_width, board_height);
-            });
-        if potential_tree(&robots, board_width, board_height) {
-            result = i + 1;
-            save_robots_bmp(&robots, board_width, board_height, i+1);
+    // frames after the first full cycle repeat earlier ones
+    let cycle = simulation::find_cycle(&simulation).map_or(0, |cycle| cycle.start + cycle.length);
+    for i in 1..=cycle {
+        simulation.step();
+        if potential_tree(&simulation.board, simulation.robots.len()) {
+            return Ok(Some((i, simulation.board)));
         }
-    });
+    }
 
-    result.to_string()
+    Ok(None)
 }
 
 #[allow(unused)]
-fn print_robots(robots: &Vec<Robot>, width: i32, height: i32, as_result: bool) -> String {
+fn print_robots(robots: &[Robot], width: i32, height: i32, as_result: bool) -> String {
     let mut board = vec![vec![0; width as usize]; height as usize];
 
     robots
         .iter()
         .for_each(|r| {
             *board
-                .get_mut(r.pos.1 as usize)
+                .get_mut(r.pos.y as usize)
                 .unwrap()
-                    .get_mut(r.pos.0 as usize)
+                    .get_mut(r.pos.x as usize)
                     .unwrap() += 1;
         });
 
@@ -197,7 +211,7 @@ fn print_robots(robots: &Vec<Robot>, width: i32, height: i32, as_result: bool) -
                     return ' ';
                 }
                 
-                return match *n {
+                match *n {
                     0 => '.',
                     r => char::from_digit(r, 10).unwrap()
                 } 
@@ -208,103 +222,97 @@ fn print_robots(robots: &Vec<Robot>, width: i32, height: i32, as_result: bool) -
 }
 
 
-fn potential_tree(robots: &Vec<Robot>, width: i32, height: i32) -> bool {
-    let mut board = vec![vec![0; width as usize]; height as usize];
-    let mut robots_num = 0;
-    robots
-        .iter()
-        .for_each(|r| {
-            *board
-                .get_mut(r.pos.1 as usize)
-                .unwrap()
-                    .get_mut(r.pos.0 as usize)
-                    .unwrap() += 1;
+// Columns repeat every `width` seconds and rows every `height` seconds. The tree
+// is drawn when robots are packed in both directions, the second of the tightest
+// columns and the second of the tightest rows give it by the Chinese Remainder Theorem.
+fn tree_candidate(robots: &[Robot], width: i32, height: i32) -> Option<usize> {
+    let tightest = |size: i32, coordinate: fn(&Vec2) -> i32| {
+        (0..size).min_by_key(|&second| {
+            spread(robots.iter().map(|r| (coordinate(&r.pos) + coordinate(&r.vel) * second).rem_euclid(size)))
+        })
+    };
 
-            robots_num += 1;
-        });
-    
-    let mut with_neighbor: HashSet<Vec2> = HashSet::new();
-
-    for x in 1..(width-1) {
-        for y in 1..(height-1) {
-            let neighbor = [(x, y-1), (x+1, y), (x, y+1), (x-1, y),
-                (x-1, y-1), (x+1, y-1), (x-1, y+1), (x+1, y+1)]
-                .iter()
-                .map(|&(x, y)| {
-                    (*board
-                        .get(y as usize)
-                        .unwrap()
-                        .get(x as usize)
-                        .unwrap() > 0) as usize
-                })
-                .sum::<usize>();
-
-            if neighbor > 7 {
-                with_neighbor.insert((x, y));
-            }
-        }
-    }
+    let columns = tightest(width, |v| v.x)?;
+    let rows = tightest(height, |v| v.y)?;
+    math::crt(&[(columns as i64, width as i64), (rows as i64, height as i64)])
+        .map(|(second, _)| second as usize)
+}
+
+// variance times the squared number of values
+fn spread(values: impl Iterator<Item = i32>) -> i64 {
+    let (count, sum, squares) = values.fold((0, 0, 0), |(count, sum, squares), v| {
+        let v = v as i64;
+        (count + 1, sum + v, squares + v * v)
+    });
 
-    with_neighbor.len() as f32 >= robots_num as f32 * 0.15
+    count * squares - sum * sum
 }
 
-fn save_robots_bmp(robots: &Vec<Robot>, width: i32, height: i32, iter: usize) {
-    use bmp::{Image, Pixel};
-    let mut img = Image::new(width as u32, height as u32);
+fn potential_tree(board: &Grid<usize>, robots_num: usize) -> bool {
+    // cells surrounded by robots from every side
+    let with_neighbor = board
+        .map(|(pos, _)| board.neighbors(pos).all(|n| board.get_at(&n) > Some(&0)))
+        .filter(|&surrounded| surrounded)
+        .count();
 
-    for (x, y) in img.coordinates() {
-        img.set_pixel(x, y, px!(x, y, 200));
+    with_neighbor as f32 >= robots_num as f32 * 0.15
+}
+
+/// Saves a board, like the one of [`find_tree`], as a PNG or BMP image.
+#[cfg(feature = "render")]
+pub fn save_board_image(board: &Grid<usize>, path: impl AsRef<std::path::Path>) -> aoclib::render::ImageResult<()> {
+    aoclib::render::save_image(board, path, 1, |&robots| {
+        match robots {
+            0 => [255, 255, 255],
+            n => [n as u8, n as u8, 200],
+        }
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct BoardParams {
+    pub board_width: i32,
+    pub board_height: i32,
+}
+
+impl Default for BoardParams {
+    fn default() -> Self {
+        BoardParams {
+            board_width: 101,
+            board_height: 103,
+        }
     }
+}
 
-    let mut board = vec![vec![0; width as usize]; height as usize];
+pub struct Day14;
 
-    robots
-        .iter()
-        .for_each(|r| {
-            *board
-                .get_mut(r.pos.1 as usize)
-                .unwrap()
-                    .get_mut(r.pos.0 as usize)
-                    .unwrap() += 1;
-        });
+impl Solution for Day14 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 14;
 
-    board
-        .iter()
-        .enumerate()
-        .for_each(|(y, line)| line
-            .iter()
-            .enumerate()
-            .for_each(|(x, n)| {
-                if n == &0 {
-                    img.set_pixel(x as u32, y as u32, px!(255, 255, 255));
-                } else {
-                    img.set_pixel(x as u32, y as u32, px!(*n, *n, 200));
-                }
-            }));
+    type Params = BoardParams;
+
+    fn part1(input: &str, params: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input, params.board_width, params.board_height).map(Answer::from)
+    }
 
-    let _ = img.save(format!("{}.bmp", iter));
+    fn part2(input: &str, params: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input, params.board_width, params.board_height).map(Answer::from)
+    }
 }
 
+aoclib::expected_tests!(Day14);
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT_SIMPLE: &str = "p=2,4 v=2,-3";
-    const INPUT: &str = "p=0,4 v=3,-3
-p=6,3 v=-1,-3
-p=10,3 v=-1,2
-p=2,0 v=2,-1
-p=0,0 v=1,3
-p=3,0 v=-2,-2
-p=7,6 v=-1,-3
-p=3,0 v=-1,-2
-p=9,3 v=2,3
-p=7,3 v=-1,2
-p=2,4 v=2,-3
-p=9,5 v=-3,-3";
 
     #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT_SIMPLE, 11, 7));
-        println!("{}", process_part1(INPUT, 11, 7));
+    fn test_robot_outside_the_board() {
+        let err = process_part1("p=0,4 v=3,-3\np=11,3 v=-1,2", 11, 7).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: expected robot on the 11x7 board, found `p=11,3 v=-1,2`");
+
+        assert!(process_part2("p=0,-1 v=3,-3", 11, 7).is_err());
     }
-}
\ No newline at end of file
+}
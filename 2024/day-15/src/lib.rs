@@ -1,7 +1,4 @@
-#[derive(PartialEq, Debug, Clone, Copy)]
-enum Direction {
-    Up, Right, Down, Left
-}
+use aoclib::{Answer, Direction, Grid, ParseError, ParseResult, Simulation, Solution, Vec2};
 
 #[derive(PartialEq, Eq, Clone)]
 enum Object {
@@ -26,60 +23,32 @@ fn object_char(obj: &Object) -> char {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-struct Vec2 {
-    x: i32,
-    y: i32,
-}
-
-impl From<(i32, i32)> for Vec2 {
-    fn from(value: (i32, i32)) -> Self {
-        Vec2{
-            x: value.0,
-            y: value.1
-        }
-    }
-}
-
-impl std::ops::Add<Vec2> for Vec2 {
-    type Output = Vec2;
-
-    fn add(self, rhs: Vec2) -> Self::Output {
-        (self.x + rhs.x, self.y + rhs.y).into()
-    }
-}
-
-impl std::ops::Mul<Vec2> for Vec2 {
-    type Output = Vec2;
-
-    fn mul(self, rhs: Vec2) -> Self::Output {
-        (self.x * rhs.x, self.y * rhs.y).into()
-    }
-}
-
-impl std::ops::Sub<Vec2> for Vec2 {
-    type Output = Vec2;
+fn char_object(c: char) -> Option<Object> {
+    use Object::*;
 
-    fn sub(self, rhs: Vec2) -> Self::Output {
-        (self.x - rhs.x, self.y - rhs.y).into()
+    match c {
+        '@' => Some(Robot),
+        '#' => Some(Wall),
+        'O' => Some(Box),
+        '[' => Some(BoxBigLeft),
+        ']' => Some(BoxBigRight),
+        '.' => Some(Empty),
+        _ => None,
     }
 }
 
-
-struct Warehouse {
+#[derive(Clone)]
+pub struct Warehouse {
     board: Grid<Object>,
     robot_pos: Vec2,
     commands: Vec<Direction>,
+    // index of the next move in `commands`
+    next_command: usize,
 }
 
 impl Warehouse {
-    fn step(&mut self, command: Direction) {
-        let direction_vec = match command {
-            Direction::Up => (0, -1),
-            Direction::Right => (1, 0),
-            Direction::Down => (0, 1),
-            Direction::Left => (-1, 0),
-        };
+    fn move_robot(&mut self, command: Direction) {
+        let direction_vec: Vec2 = command.offset();
 
         let current_pos = self.robot_pos;
         let mut queue = vec![current_pos];
@@ -89,7 +58,7 @@ impl Warehouse {
         loop {
             let mut cur_level = vec![];
             for i in 0..last_level.len() {
-                let current_pos = last_level.get(i).unwrap().to_owned() + direction_vec.into();
+                let current_pos = last_level.get(i).unwrap().to_owned() + direction_vec;
                 
                 let current_obj =  self.board.get_at(&current_pos);
 
@@ -156,7 +125,7 @@ impl Warehouse {
                 continue
             }
 
-            let new_point_coord = *point+direction_vec.into();
+            let new_point_coord = *point+direction_vec;
 
             self.board.put_at(new_point_coord, cur_item.clone());
             self.board.put_at(*point, Object::Empty);
@@ -179,119 +148,39 @@ impl Warehouse {
     }
 }
 
-struct Grid<T> {
-    data: Vec<T>,
-    width: i32,
-    height: i32,
-}
-
-impl<'this, T> Grid<T> {
-    pub fn from_string_with_map<F>(input: &str, map_func: F) -> Self
-        where F: Fn(char) -> T {
-        
-        let height = input.lines().count();
-        let width = input.lines().nth(0).unwrap_or("").len();
-
-        let data: Vec<T> = input
-            .lines()
-            .flat_map(|line| {
-                line
-                    .chars()
-                    .map(&map_func)
-                    .collect::<Vec<T>>()
-            })
-            .collect();
-
-        Grid { data, width: width as i32, height: height as i32 }
-    }
-
-    pub fn get_at(&self, p: &Vec2) -> Option<&T> {
-        if p.x > self.width || p.y > self.height {
-            None
-        } else { 
-            self.data.get(self.point2pos(p))
-        }
-    }
-
-    pub fn find<F>(&'this self, f: F) -> impl Iterator<Item = (usize, &'this T)>
-        where F: Fn(&'this T) -> bool
-    {
-        self
-            .data
-            .iter()
-            .enumerate()
-            .filter(move |&v| f(v.1))
-    }
-
-    pub fn find_one<F>(&'this self, f: F) -> Option<Vec2>
-        where F: Fn(&'this T) -> bool
-    {
-        self
-            .find(f)
-            .nth(0)
-            .map(|(pos, _)| self.pos2point(pos))
-    }
-
-    pub fn swap_cells(&mut self, from: &Vec2, to: &Vec2) {
-        let from_pos = self.point2pos(from);
-        let to_pos = self.point2pos(to);
-
-        self.data.swap(from_pos, to_pos);
-    }
-
-    pub fn put_at(&mut self, point: Vec2, item: T) {
-        let pos = self.point2pos(&point);
-        *self.data.get_mut(pos).unwrap() = item;
-    }
-
-    pub fn visualization<F>(&self, map_func: F) -> String 
-        where F: Fn(&T) -> char
-    {
-        self
-            .data
-            .chunks(self.width as usize)
-            .map(|c| {
-                c.iter().map(&map_func).collect::<String>()
-            })
-            .collect::<Vec<String>>()
-            .join("\n")
-    }
+// every step makes the next move from the list
+impl Simulation for Warehouse {
+    // moves are never repeated, so the position in the list tells the states apart
+    type State = usize;
 
-    pub fn is_point_in_boundaries(&self, point: &Vec2) -> bool {
-        point.x >= 0 && point.y >= 0
-            && point.x < self.width && point.y < self.height
-    }
+    fn step(&mut self) -> bool {
+        let Some(&command) = self.commands.get(self.next_command) else {
+            return false;
+        };
 
-    pub fn map<F, R>(&'this self, f: F) -> impl Iterator<Item = R> + 'this
-        where F: Fn((Vec2, &'this T)) -> R + 'this
-    {
-        self.data
-            .iter()
-            .enumerate()
-            .map(move |(pos, val)| {
-                let point = self.pos2point(pos);
-                f((point, val))
-            })
+        self.next_command += 1;
+        self.move_robot(command);
+        true
     }
 
-    fn point2pos(&self, p: &Vec2) -> usize {
-        (p.x + p.y * self.width) as usize
+    fn state(&self) -> Self::State {
+        self.next_command
     }
-    
-    fn pos2point(&self, pos: usize) -> Vec2 {
-        let y = pos as i32 / self.width;
-        let x = pos as i32 % self.width;
 
-        (x, y).into()
+    fn render(&self) -> String {
+        self.board.visualization(object_char)
     }
 }
 
-fn parse_input(input: &str, with_resize: bool) -> Warehouse {
-    let input_parts: Vec<&str> = input.split("\n\n").collect();
-    let grid_str = {
-        let original_grid = input_parts.first().unwrap().to_owned();
+pub fn parse_input(input: &str, with_resize: bool) -> ParseResult<Warehouse> {
+    let (original_grid, commands_section) = input.split_once("\n\n")
+        .ok_or_else(|| ParseError::at(input, &input[input.len()..], "empty line before moves"))?;
+    // `original_grid` starts the input, so error positions already match it
+    let original = Grid::try_from_string_with_map(original_grid, "`#`, `O`, `.` or `@`", char_object)?;
+
+    let grid = {
         if !with_resize {
-            original_grid.to_string()
+            original
         } else {
             let mut new_grid = String::with_capacity(original_grid.len()*2);
 
@@ -307,151 +196,68 @@ fn parse_input(input: &str, with_resize: bool) -> Warehouse {
                     }
                 });
 
-            new_grid
+            Grid::from_string_with_map(new_grid.as_str(), |c| char_object(c).unwrap_or(Object::Empty))
         }
     };
 
-    let commands = input_parts
-        .get(1)
-        .unwrap()
-        .chars()
-        .filter(|&c| "^>v<".contains(c))
-        .map(|c| {
-            match c {
-                '^' => Direction::Up,
-                '>' => Direction::Right,
-                'v' => Direction::Down,
-                _ => Direction::Left,
-            }
+    let commands = commands_section
+        .char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(pos, c)| {
+            Direction::from_char(c)
+                .ok_or_else(|| ParseError::at(input, &commands_section[pos..pos+c.len_utf8()], "move `^`, `>`, `v` or `<`"))
         })
-        .collect();
-
-    let grid = Grid::from_string_with_map(grid_str.as_str(), |c| {
-        match c {
-            '#' => Object::Wall,
-            'O' => Object::Box,
-            '@' => Object::Robot,
-            '[' => Object::BoxBigLeft,
-            ']' => Object::BoxBigRight,
-            _ => Object::Empty,
-        }
-    });
+        .collect::<ParseResult<Vec<Direction>>>()?;
 
-    Warehouse{
-        robot_pos: grid.find_one(|i| *i == Object::Robot).unwrap(),
+    Ok(Warehouse{
+        robot_pos: grid.find_one(|i| *i == Object::Robot)
+            .ok_or_else(|| ParseError::at(input, original_grid, "robot `@` in the warehouse"))?,
         board: grid,
         commands,
-    }
+        next_command: 0,
+    })
 }
 
-pub fn process_part1(input: &str) -> String {
-    let mut warehouse = parse_input(input, false);
-
-    warehouse
-        .commands
-        .clone()
-        .iter()
-        .for_each(|cmd| warehouse.step(*cmd));
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let mut warehouse = parse_input(input, false)?;
+    while warehouse.step() {}
 
 
-    warehouse.gps_result().to_string()
+    Ok(warehouse.gps_result().to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let mut warehouse = parse_input(input, true);
-
-    warehouse
-        .commands
-        .clone()
-        .iter()
-        .for_each(|cmd| warehouse.step(*cmd));
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let mut warehouse = parse_input(input, true)?;
+    while warehouse.step() {}
     
-    warehouse.gps_result().to_string()
+    Ok(warehouse.gps_result().to_string())
 }
 
 
 pub fn debug_p2(input: &str) -> String {
-    let mut warehouse = parse_input(input, false);
+    let mut warehouse = parse_input(input, false).unwrap();
 
     println!("{}", warehouse.board.visualization(object_char));
-    warehouse.step(Direction::Up);
+    warehouse.move_robot(Direction::Up);
     "".to_string()
 }
 
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT_SIMPLE: &str = "########
-#..O.O.#
-##@.O..#
-#...O..#
-#.#.O..#
-#...O..#
-#......#
-########
-
-<^^>>>vv<v>>v<<";
-    const INPUT: &str = "##########
-#..O..O.O#
-#......O.#
-#.OO..O.O#
-#..O@..O.#
-#O#..O...#
-#O..O..O.#
-#.OO.O.OO#
-#....O...#
-##########
-
-<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
-vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
-><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
-<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
-^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
-^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
->^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
-<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
-^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
-
-    const INPUT_SIMPLE2: &str = "#######
-#...#.#
-#.....#
-#..OO@#
-#..O..#
-#.....#
-#######
-
-<vv<<^^<<^^";
-
-    #[test]
-    fn test_process_part1() {
-        // println!("{}", process_part1(INPUT_SIMPLE));
-        println!("{}", process_part1(INPUT));
-    }
-    
-    #[test]
-    fn test_process_part2() {
-        // println!("{}", process_part2(INPUT_SIMPLE2));
-        println!("{}", process_part2(INPUT));
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 15;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
-    const INPUT_DEBUG: &str = "###############
-####....[]...##
-##....[]....[##
-##....[][]...##
-##.....[]....##
-##[]....@....##
-##[][].......##
-##..........[##
-##..##.......##
-##.[][]...[][##
-###############
-
-v><>";
-    #[test]
-    fn test_debug_p2() {
-        println!("{}", debug_p2(INPUT_DEBUG));
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
 }
 
-
+aoclib::expected_tests!(Day15);
@@ -1,13 +1,10 @@
+use aoclib::{Answer, ParseResult, Solution};
+use aoclib::parse::{expect, integer, labelled, literal, paragraph_break, parse_all, IResult};
 use nom::{
-    bytes::{
-        complete::tag, 
-        streaming::is_a
-    }, 
-    character::complete::{digit1, newline}, 
-    combinator::{map, opt}, 
-    multi::{fold_many1, many1}, 
+    character::complete::{char, line_ending}, 
+    combinator::{cut, map, verify}, 
+    multi::separated_list1, 
     sequence::{preceded, terminated, tuple}, 
-    IResult
 };
 
 #[derive(Debug, Clone)]
@@ -39,7 +36,7 @@ impl Computer {
     }
 
     fn bxl(&mut self, op: u8) { // 1
-        self.register_b = self.register_b ^ op as i128;
+        self.register_b ^= op as i128;
         self.pc += 2;
     }
 
@@ -57,7 +54,7 @@ impl Computer {
     }
 
     fn bxc(&mut self, _op: u8) { // 4
-        self.register_b = self.register_b ^ self.register_c;
+        self.register_b ^= self.register_c;
         self.pc += 2;
     }
 
@@ -77,11 +74,11 @@ impl Computer {
 
     fn combo_op(&self, op: u8) -> i128 {
         match op {
-            1|2|3 => op as i128,
+            0..=3 => op as i128,
             4 => self.register_a,
             5 => self.register_b,
             6 => self.register_c,
-            _ => panic!("Invalid operand")
+            _ => unreachable!("the parser rejects combo operand {}", op),
         }
     }
 
@@ -96,8 +93,9 @@ impl Computer {
     }
 
     pub fn tick(&mut self) {
-        let instruction = self.program.get(self.pc).unwrap();
-        let op = self.program.get(self.pc+1).unwrap().to_owned();
+        // the parser only accepts programs of whole instructions
+        let instruction = self.program[self.pc];
+        let op = self.program[self.pc+1];
 
         match instruction {
             0 => self.adv(op),
@@ -108,7 +106,7 @@ impl Computer {
             5 => self.out(op),
             6 => self.bdv(op),
             7 => self.cdv(op),
-            _ => panic!("Invalid op")
+            _ => unreachable!("the parser rejects opcode {}", instruction),
         }
     }
 
@@ -147,53 +145,45 @@ impl Computer {
 
 }
 
-fn computer_parser(input: &str) -> IResult<&str, Computer> {
-    map(
-        tuple((
-                terminated(
-                    preceded(
-                        tag("Register A: "), 
-                        digit1,
-                    ),
-                    newline,
-                ),
+// opcode and operand, combo operands stop at 6
+fn instruction_parser(input: &str) -> IResult<'_, [u8; 2]> {
+    let (input, opcode) = expect("opcode 0 to 7", verify(integer, |&v: &u8| v < 8))(input)?;
 
-                terminated(
-                    preceded(
-                        tag("Register B: "), 
-                        digit1,
-                    ),
-                    newline,
-                ),
+    let (label, max) = match opcode {
+        0 | 2 | 5 | 6 | 7 => ("combo operand 0 to 6", 6),
+        _ => ("operand 0 to 7", 7),
+    };
+    let (input, operand) = preceded(
+        expect(label, char(',')),
+        expect(label, verify(integer, |&v: &u8| v <= max)),
+    )(input)?;
 
-                terminated(
-                    preceded(
-                        tag("Register C: "), 
-                        digit1,
-                    ),
-                    newline,
-                ),
+    Ok((input, [opcode, operand]))
+}
+
+fn computer_parser(input: &str) -> IResult<'_, Computer> {
+    map(
+        tuple((
+                terminated(labelled("Register A", integer), line_ending),
+                terminated(labelled("Register B", integer), line_ending),
+                labelled("Register C", integer),
 
                 preceded(
-                    tuple((many1(is_a(" \n")), tag("Program: "))), 
-                    fold_many1(
-                        terminated(digit1, opt(tag(","))),
-                        Vec::new,
-                        |mut acc: Vec<u8>, item: &str| {
-                            acc.push(item.parse().unwrap());
-                            acc
-                        }
+                    paragraph_break,
+                    labelled(
+                        "Program",
+                        separated_list1(literal(","), cut(instruction_parser)),
                     ),
                 )
         )),
 
-        |(reg_a, reg_b, reg_c, prog): (&str, &str, &str, Vec<u8>)| {
+        |(register_a, register_b, register_c, prog)| {
             Computer{
-                register_a: reg_a.parse::<i128>().unwrap(),
-                register_b: reg_b.parse::<i128>().unwrap(),
-                register_c: reg_c.parse::<i128>().unwrap(),
+                register_a,
+                register_b,
+                register_c,
 
-                program: prog,
+                program: prog.concat(),
                 pc: 0,
                 output: vec![],
 
@@ -203,20 +193,18 @@ fn computer_parser(input: &str) -> IResult<&str, Computer> {
     )(input)
 }
 
-fn parse_input(input: &str) -> Computer {
-    let (_, computer) = computer_parser(input).unwrap();
-
-    computer
+fn parse_input(input: &str) -> ParseResult<Computer> {
+    parse_all(input, computer_parser)
 }
 
-pub fn process_part1(input: &str) -> String {
-    let mut computer = parse_input(input);
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let mut computer = parse_input(input)?;
     computer.run();    
-    computer.get_output()
+    Ok(computer.get_output())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let mut computer = parse_input(input);
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let mut computer = parse_input(input)?;
 
     let is_valid_output = |program: &Vec<u8>, output: &Vec<u8>| {
         if output.len() > program.len() {
@@ -241,16 +229,35 @@ pub fn process_part2(input: &str) -> String {
             if computer.get_output() == computer.get_program() {
                 break;
             }
-            new_register_a = new_register_a*computer.a_multiplier;
+            new_register_a *= computer.a_multiplier;
         } else {
             new_register_a+=1;
         }
     }
 
     
-    new_register_a.to_string()
+    Ok(new_register_a.to_string())
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 17;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
 }
 
+aoclib::expected_tests!(Day17);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,19 +267,22 @@ Register C: 0
 
 Program: 0,1,5,4,3,0";
 
-    const INPUT2: &str = "Register A: 2024
-Register B: 0
-Register C: 0
+    #[test]
+    fn test_invalid_opcode() {
+        let err = process_part1(&INPUT.replace("0,1,5", "0,9,5")).unwrap_err();
 
-Program: 0,3,5,4,3,0";
+        assert_eq!(err.to_string(), "line 5, column 12: expected combo operand 0 to 6, found `9,5,4,3,0`");
 
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
-    }
-    
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT2));
+        let err = process_part1(&INPUT.replace("0,1,5", "8,1,5")).unwrap_err();
+        assert_eq!(err.to_string(), "line 5, column 10: expected opcode 0 to 7, found `8,1,5,4,3,0`");
+
+        let err = process_part1(&INPUT.replace("0,1,5", "0,7,5")).unwrap_err();
+        assert_eq!(err.to_string(), "line 5, column 12: expected combo operand 0 to 6, found `7,5,4,3,0`");
+
+        // literal operands go up to 7
+        assert!(process_part1(&INPUT.replace("0,1,5", "1,7,0,1,5")).is_ok());
+
+        let err = process_part1(&INPUT.replace(",3,0", ",3")).unwrap_err();
+        assert_eq!(err.to_string(), "line 5, column 19: expected operand 0 to 7, found end of input");
     }
 }
\ No newline at end of file
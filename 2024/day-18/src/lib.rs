@@ -1,5 +1,6 @@
-mod aoclib;
-use crate::aoclib::*;
+use aoclib::*;
+use aoclib::parse::{lines, parse_all, vec2};
+use serde::Deserialize;
 
 type Memory = Vec<Vec2>;
 
@@ -10,27 +11,12 @@ enum MemoryCell {
 }
 
 
-fn parse_input(input: &str) -> Memory {
-    input
-        .lines()
-        .filter_map(|l| {
-            let elements: Vec<&str> = l.split(",").collect();
-
-            if elements.len() < 2 {
-                None
-            } else {
-                Some((
-                    elements.get(0).unwrap().parse::<i32>().unwrap(), 
-                    elements.get(1).unwrap().parse::<i32>().unwrap(),
-                ).into())
-            }
-        })
-        .collect()
-
+fn parse_input(input: &str) -> ParseResult<Memory> {
+    parse_all(input, lines(vec2))
 }
 
-pub fn process_part1(input: &str, board_size: i32, memory_bytes: usize) -> String {
-    let memory = parse_input(input);
+pub fn process_part1(input: &str, board_size: i32, memory_bytes: usize) -> ParseResult<String> {
+    let memory = parse_input(input)?;
 
     let graph = Grid::new_empty_with_x_y_map(
         board_size, 
@@ -53,20 +39,21 @@ pub fn process_part1(input: &str, board_size: i32, memory_bytes: usize) -> Strin
         }
     );
 
-    let distance = graph.dijkstra(
+    let distance = graph.bfs(
         (0,0).into(),
         graph.size() - (1, 1).into(),
         |_, cell| cell == &MemoryCell::Free,
-        |_, _| {1},
     );
 
-    distance.unwrap().to_string()
+    // no path to the exit, like part 2 when the exit is never cut off
+    Ok(distance.map_or("-1".to_string(), |distance| distance.cost.to_string()))
 }
 
-pub fn process_part2(input: &str, board_size: i32) -> String {
-    let memory = parse_input(input);
+pub fn process_part2(input: &str, board_size: i32, memory_bytes: usize) -> ParseResult<String> {
+    let memory = parse_input(input)?;
 
-    for i in 404..memory.len() {
+    // the first `memory_bytes` still leave a path, see part 1
+    for i in memory_bytes..memory.len() {
         let graph = Grid::new_empty_with_x_y_map(
             board_size, 
             board_size,
@@ -87,67 +74,72 @@ pub fn process_part2(input: &str, board_size: i32) -> String {
                 }
             }
         );
-
-        // println!("{}", graph.visualization(|cell| {
-        //     match cell {
-        //         MemoryCell::Free => '.',
-        //         MemoryCell::Corrupted => '#',
-        //     }
-        // }));
     
-        println!("{}", i);
-        let distance = graph.dijkstra(
+        let distance = graph.bfs(
             (0,0).into(),
             graph.size() - (1, 1).into(),
             |_, cell| cell == &MemoryCell::Free,
-            |_, _| {1},
         );
 
         if distance.is_none() {
             let res = memory.get(i-1).unwrap();
-            return format!("{},{}", res.x, res.y);
+            return Ok(format!("{},{}", res.x, res.y));
         }
     }
     
-    "-1".to_string()
+    Ok("-1".to_string())
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct MemoryParams {
+    pub board_size: i32,
+    pub memory_bytes: usize,
+}
+
+impl Default for MemoryParams {
+    fn default() -> Self {
+        MemoryParams {
+            board_size: 71,
+            memory_bytes: 1024,
+        }
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 18;
+
+    type Params = MemoryParams;
+
+    fn part1(input: &str, params: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input, params.board_size, params.memory_bytes).map(Answer::from)
+    }
+
+    fn part2(input: &str, params: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input, params.board_size, params.memory_bytes).map(Answer::from)
+    }
 }
 
+aoclib::expected_tests!(Day18);
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "5,4
-4,2
-4,5
-3,0
-2,1
-6,3
-2,4
-1,5
-0,6
-3,3
-2,6
-5,1
-1,2
-5,5
-2,5
-6,5
-1,4
-0,4
-6,4
-1,1
-6,1
-1,0
-0,5
-1,6
-2,0";
 
     #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT, 7, 12));
+    fn test_invalid_coordinates() {
+        let err = process_part1("5,4\n4;2", 7, 12).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: expected coordinates like `5,4`, found `4;2`");
+
+        let err = process_part1("5,4\n4,-", 7, 12).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 3: expected y coordinate, found `-`");
     }
-    
+
     #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT, 7));
+    fn test_blocked_exit() {
+        assert_eq!(process_part1("1,0\n0,1", 3, 2), Ok("-1".to_string()));
     }
 }
\ No newline at end of file
@@ -1,28 +1,47 @@
-use std::collections::HashMap;
+use aoclib::{Answer, Memo, ParseError, ParseResult, Solution};
 
 use rayon::prelude::*;
 
 type Towels<'a> = Vec<&'a str>;
 type Designs<'a> = Vec<&'a str>;
 
-fn parse_input(input: &str) -> (Towels, Designs) {
-   let lines = input.lines();
+fn parse_input(input: &str) -> ParseResult<(Towels<'_>, Designs<'_>)> {
+    let is_stripe = |c: char| "wubrg".contains(c);
+    let check_stripes = |pattern: &str| match pattern.find(|c| !is_stripe(c)) {
+        Some(pos) => Err(ParseError::at(input, &pattern[pos..], "stripe color w, u, b, r or g")),
+        None => Ok(()),
+    };
 
-   let towels = lines.clone()
+    let lines = input.lines();
+
+    let towels: Towels = lines.clone()
         .take(1)
         .flat_map(|towel| towel.split(", "))
         .collect();
+    for towel in towels.iter() {
+        if towel.is_empty() {
+            return Err(ParseError::at(input, towel, "towel pattern"));
+        }
+        check_stripes(towel)?;
+    }
 
-    let designs = lines
+    if let Some(separator) = lines.clone().nth(1).filter(|line| !line.is_empty()) {
+        return Err(ParseError::at(input, separator, "empty line before designs"));
+    }
+
+    let designs: Designs = lines
         .skip(2)
-        .filter(|design| design.len() > 0)
+        .filter(|design| !design.is_empty())
         .collect();
+    for design in designs.iter() {
+        check_stripes(design)?;
+    }
 
-    (towels, designs)
+    Ok((towels, designs))
 }
 
 fn can_build(design: &str, towels: &Vec<&str>, num: &mut i32) -> bool {
-    if design.len() < 1 {
+    if design.is_empty() {
         *num += 1;
         return true;
     }
@@ -33,83 +52,47 @@ fn can_build(design: &str, towels: &Vec<&str>, num: &mut i32) -> bool {
         }
     }
 
-    return false
+    false
 }
 
 
 
-fn count_possibilities<'a>(
-    design: &'a str,
-    towels: &Vec<&str>,
-    cache: &mut HashMap<&'a str, usize>,
-) -> usize {
-    if design.len() == 0 {
-        return 1;
-    }
-
-    if let Some(&count_possibilities) = cache.get(design) {
-        return count_possibilities;
-    }
+fn count_possibilities(design: &str, towels: &[&str]) -> usize {
+    let mut count = Memo::<&str, usize>::new().wrap(|count, design| {
+        if design.is_empty() {
+            return 1;
+        }
 
-    let possibilities = towels
-        .iter()
-        .filter(|p| design.starts_with(*p))
-        .map(|p| count_possibilities(&design[p.len()..], towels, cache))
-        .sum();
+        towels
+            .iter()
+            .filter(|p| design.starts_with(*p))
+            .map(|p| count(&design[p.len()..]))
+            .sum()
+    });
 
-    cache.insert(design, possibilities);
-    possibilities
+    count.call(design)
 }
 
-pub fn process_part1(input: &str) -> String {
-    let (towels, designs) = parse_input(input);
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let (towels, designs) = parse_input(input)?;
 
-    designs
+    Ok(designs
         .iter()
         .filter(|design| {
             let mut num = 0;
             can_build(design, &towels, &mut num)
         })
         .count()
-        .to_string()
+        .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let (towels, designs) = parse_input(input);
-    designs
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let (towels, designs) = parse_input(input)?;
+    Ok(designs
         .par_iter()
-        .map(|design| {
-            let mut cache = HashMap::new();
-            
-            count_possibilities(design, &towels, &mut cache) as usize
-        })
+        .map(|design| count_possibilities(design, &towels))
         .sum::<usize>()
-        .to_string()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "r, wr, b, g, bwu, rb, gb, br
-
-brwrr
-bggr
-gbbr
-rrbgbr
-ubwu
-bwurrg
-brgr
-bbrgwb";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
-    }
-    
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
-    }
+        .to_string())
 }
 // The staff don't really like some of the towel arrangements you came up with. To avoid an endless cycle of towel rearrangement, maybe you should just give them every possible option.
 
@@ -145,15 +128,21 @@ bbrgwb";
 
 // They'll let you into the onsen as soon as you have the list. What do you get if you add up the number of different ways you could make each design?
 
-fn solve2(design: &str, towels: &Vec<&str>) {
-    let mut found_positions: Vec<usize> = vec![0];
-    for (index, cur_design_letter) in design.chars().enumerate() {
-        let mut cur = 0;
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 19;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-    //     cur = 0
-    //     for j in range(index + 1):
-    //         if goal[j:index + 1] in choices and dp[j]:
-    //             cur += dp[j]
-    //     dp.append(cur)
-    // return dp[-1]
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day19);
@@ -1,9 +1,6 @@
-mod aoclib;
-
-use itertools::Itertools;
 use rayon::prelude::*;
 
-use crate::aoclib::*;
+use aoclib::*;
 
 #[derive(PartialEq, Eq)]
 enum RacetrackElement {
@@ -13,122 +10,92 @@ enum RacetrackElement {
     Wall,
 }
 
-
-pub fn process_part1(input: &str) -> String {
-    let graph = Grid::from_string_with_map(
+fn parse_input(input: &str) -> ParseResult<(Grid<RacetrackElement>, Vec2, Vec2)> {
+    let graph = Grid::try_from_string_with_map(
         input,
+        "`#`, `.`, `S` or `E`",
         |c| {
             match c {
-                '#' => RacetrackElement::Wall,
-                'S' => RacetrackElement::Start,
-                'E' => RacetrackElement::End,
-                _ => RacetrackElement::Free,
+                '#' => Some(RacetrackElement::Wall),
+                'S' => Some(RacetrackElement::Start),
+                'E' => Some(RacetrackElement::End),
+                '.' => Some(RacetrackElement::Free),
+                _ => None,
             }
         }
-    );
-
-    let start = graph.find_one(|item| item == &RacetrackElement::Start).unwrap();
-    let end = graph.find_one(|item| item == &RacetrackElement::End).unwrap();
-
-    let reference = graph.dijkstra(
-        start, 
-        end, 
-        |_, item| [RacetrackElement::Free, RacetrackElement::End].contains(&item), 
-        |_, _| {1},
-    ).unwrap();
-
-    (0..graph.raw_size())
-        .par_bridge()
-        .map(|i| {
-            if graph.get_at(&graph.pos2point(i)).unwrap() != &RacetrackElement::Wall {
-                return 0
-            }
-    
-            let cur_size = graph.dijkstra(
-                start, 
-                end, 
-                |pos, item| {
-                    [RacetrackElement::Free, RacetrackElement::End].contains(&item)
-                        || graph.point2pos(pos) == i
-                }, 
-                |_, _| {1},
-            );
-
-            let saved = reference.cost - cur_size.unwrap().cost;
-            
-            (saved >= 100) as i32
-        })
-        .sum::<i32>()
-        .to_string()
+    )?;
+
+    let start = graph.find_one(|item| item == &RacetrackElement::Start)
+        .ok_or_else(|| ParseError::at(input, &input[input.len()..], "start `S`"))?;
+    let end = graph.find_one(|item| item == &RacetrackElement::End)
+        .ok_or_else(|| ParseError::at(input, &input[input.len()..], "end `E`"))?;
+
+    Ok((graph, start, end))
 }
 
-pub fn process_part2(input: &str) -> String {
-    let graph = Grid::from_string_with_map(
-        input,
-        |c| {
-            match c {
-                '#' => RacetrackElement::Wall,
-                'S' => RacetrackElement::Start,
-                'E' => RacetrackElement::End,
-                _ => RacetrackElement::Free,
-            }
-        }
-    );
-
-    let start = graph.find_one(|item| item == &RacetrackElement::Start).unwrap();
-    let end = graph.find_one(|item| item == &RacetrackElement::End).unwrap();
-
-    let reference = graph.dijkstra(
-        start, 
-        end, 
-        |_, item| [RacetrackElement::Free, RacetrackElement::End].contains(&item), 
-        |_, _| {1},
-    ).unwrap();
-
-    let distance = |p1: &Vec2, p2: &Vec2| (p1.x - p2.x).abs() + (p1.y - p2.y).abs();
-
-    reference.path.iter()
-        .cartesian_product(reference.path.iter())
-        .map(|(s1, s2)| {
-            if s1.idx < s2.idx { return 0; }
-            let p1 = graph.pos2point(s1.idx);
-            let p2 = graph.pos2point(s2.idx);
-
-            let dist = distance(&p1, &p2);
-            let saves = (s2.cost - s1.cost).abs()-dist;
-            (dist <= 20 && saves >= 100) as i32
+// A cheat lets the program pass through walls for up to `max_cheat` moves. The
+// time saved is the reference time minus the time to reach the cheat start, the
+// cheat itself and the time from the cheat end to the finish.
+fn count_cheats(graph: &Grid<RacetrackElement>, start: Vec2, end: Vec2, max_cheat: i32, min_saving: i32) -> usize {
+    let is_track = |_: &Vec2, item: &RacetrackElement| item != &RacetrackElement::Wall;
+    let from_start = graph.distances_from(start, is_track);
+    let to_end = graph.distances_from(end, is_track);
+
+    let Some(&Some(reference)) = from_start.get_at(&end) else {
+        return 0;
+    };
+
+    let cheat_starts = from_start
+        .map(|(point, dist)| (point, *dist))
+        .filter_map(|(point, dist)| dist.map(|dist| (point, dist)))
+        .collect::<Vec<(Vec2, i32)>>();
+
+    cheat_starts.par_iter()
+        .map(|&(point, dist)| {
+            (-max_cheat..=max_cheat)
+                .flat_map(|dx| {
+                    let max_dy = max_cheat - dx.abs();
+                    (-max_dy..=max_dy).map(move |dy| (dx, dy))
+                })
+                .filter(|&(dx, dy)| {
+                    let Some(&Some(remaining)) = to_end.get_at(&(point + (dx, dy).into())) else {
+                        return false;
+                    };
+
+                    reference - (dist + dx.abs() + dy.abs() + remaining) >= min_saving
+                })
+                .count()
         })
-        .sum::<i32>()
-        .to_string()
+        .sum()
+}
+
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let (graph, start, end) = parse_input(input)?;
+
+    Ok(count_cheats(&graph, start, end, 2, 100).to_string())
+}
+
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let (graph, start, end) = parse_input(input)?;
 
+    Ok(count_cheats(&graph, start, end, 20, 100).to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "###############
-#...#...#.....#
-#.#.#.#.#.###.#
-#S#...#.#.#...#
-#######.#.#.###
-#######.#.#...#
-#######.#.###.#
-###..E#...#...#
-###.#######.###
-#...###...#...#
-#.#####.#.###.#
-#.#...#.#.#...#
-#.#.#.#.#.#.###
-#...#...#...###
-###############";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 20;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
-    
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day20);
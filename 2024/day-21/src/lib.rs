@@ -1,9 +1,7 @@
-#![feature(iter_map_windows)]
-
+use itertools::Itertools;
 use lazy_static::lazy_static;
-use std::{collections::BTreeMap, i32};
+use std::collections::BTreeMap;
 
-mod aoclib;
 use aoclib::*;
 type Keyboard = BTreeMap<char, Vec2>;
 
@@ -79,14 +77,21 @@ struct Code<'a> {
     num: i32,
 }
 
-fn parse_input(input: &str) -> Vec<Code<'_>> {
+fn parse_input(input: &str) -> ParseResult<Vec<Code<'_>>> {
     input
         .lines()
         .map(|num| {
-            Code{
+            if let Some(pos) = num.find(|c: char| !c.is_ascii_digit() && c != 'A') {
+                return Err(ParseError::at(input, &num[pos..], "digit or `A`"));
+            }
+            if !num.ends_with('A') {
+                return Err(ParseError::at(input, &num[num.len()..], "door code ending with `A`"));
+            }
+
+            Ok(Code{
                 code: num,
                 num: str_to_int(num),
-            }
+            })
         })
         .collect()
 }
@@ -127,16 +132,14 @@ fn available_paths_new(keyboard_grid: &BTreeMap<Vec2, char>, from_pos: Vec2, to_
 }
 
 
-fn shortest_path_at_layer(from_c: char, to_c: char, layer: i32, first_layer: bool, cache: &mut BTreeMap<(char, char, i32), u128>) -> u128 {
+type Press = (char, char, i32, bool);
+
+// called through `Memo::wrap`, `shortest` is the memoised function itself
+fn shortest_path_at_layer(shortest: &mut dyn FnMut(Press) -> u128, (from_c, to_c, layer, first_layer): Press) -> u128 {
     if layer == 0 {
         return 1;
     }
 
-    let cache_key = (from_c, to_c, layer);
-    if cache.contains_key(&cache_key) {
-        return cache.get(&cache_key).unwrap().to_owned();
-    }
-
     let keyboard: &Keyboard = match first_layer {
         false => &ARROW_PAD,
         true => &NUM_PAD,
@@ -149,7 +152,6 @@ fn shortest_path_at_layer(from_c: char, to_c: char, layer: i32, first_layer: boo
     };
 
 
-    // let cache_key = ()
     let paths_between_keys = available_paths_new(keyboard_grid, keyboard[&from_c], keyboard[&to_c]);
 
     let mut shortest_path = u128::MAX;
@@ -158,15 +160,15 @@ fn shortest_path_at_layer(from_c: char, to_c: char, layer: i32, first_layer: boo
 
         let sum = path
             .chars()
-            .map_windows(|&[from_c, to_c]| {
-                shortest_path_at_layer(from_c, to_c, layer-1, false, cache)
+            .tuple_windows()
+            .map(|(from_c, to_c)| {
+                shortest((from_c, to_c, layer-1, false))
             })
             .sum::<u128>();
 
         shortest_path = shortest_path.min(sum);
     }
 
-    cache.insert(cache_key, shortest_path);
     shortest_path
 }
 
@@ -175,53 +177,51 @@ fn shortest_path_at_layer(from_c: char, to_c: char, layer: i32, first_layer: boo
 fn solve_code(code: &str, depth: i32) -> u128 {
     let code = ["A", code].join("");
 
-    let mut cache = BTreeMap::new();
+    let mut shortest = Memo::new().wrap(shortest_path_at_layer);
     code
         .chars()
-        .map_windows(|&[from_c, to_c]| {
-            shortest_path_at_layer(from_c, to_c, depth, true, &mut cache)
+        .tuple_windows()
+        .map(|(from_c, to_c)| {
+            shortest.call((from_c, to_c, depth, true))
         })
         .sum::<u128>()
 }
 
-pub fn process_part1(input: &str) -> String {
-    let codes = parse_input(input);
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let codes = parse_input(input)?;
 
-    codes.iter().map(|c: &Code<'_>| {
+    Ok(codes.iter().map(|c: &Code<'_>| {
         let res = solve_code(c.code, 3);
         res * c.num as u128
     }).sum::<u128>()
-    .to_string()
+    .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    
-    let codes = parse_input(input);
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let codes = parse_input(input)?;
 
-    codes.iter().map(|c: &Code<'_>| {
+    Ok(codes.iter().map(|c: &Code<'_>| {
         let res = solve_code(c.code, 26);
         res * c.num as u128
     }).sum::<u128>()
-    .to_string()
-
+    .to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "029A
-980A
-179A
-456A
-379A";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+pub struct Day21;
+
+impl Solution for Day21 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 21;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
-    
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day21);
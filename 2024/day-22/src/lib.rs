@@ -1,14 +1,12 @@
-#![feature(iter_map_windows)]
-mod aoclib;
-use std::{collections::{BTreeSet, HashMap}};
+use aoclib::{parse::parse_token, Answer, CircularBuffer, ParseResult, Solution};
 
 use rayon::prelude::*;
 
-fn parse_input(input: &str) -> Vec<u128> {
+fn parse_input(input: &str) -> ParseResult<Vec<u128>> {
     input
         .trim()
         .lines()
-        .map(|l| l.parse::<u128>().unwrap())
+        .map(|l| parse_token(input, l, "initial secret number"))
         .collect()
 }
 
@@ -36,87 +34,82 @@ fn nth_secret(initial_secret: u128, nth: usize) -> u128 {
 }
 
 
-fn prices_diff(initial_secret: u128, nth: usize, registry: &mut HashMap<(i8, i8, i8, i8), i32>) {
+// price changes are in -9..=9, so a sequence of four is a number in base 19
+const CHANGE_BASE: u64 = 19;
+const SEQUENCES: usize = (CHANGE_BASE * CHANGE_BASE * CHANGE_BASE * CHANGE_BASE) as usize;
+
+// adds the price sold at for every sequence of changes to `registry`, indexed by the sequence hash
+fn prices_diff(initial_secret: u128, nth: usize, registry: &mut [i32]) {
     let mut cur_secret = initial_secret;
-    let mut seq: (i8, i8, i8, i8) = (0, 0, 0, (initial_secret%10) as i8);
-    let mut seen: BTreeSet<(i8, i8, i8, i8)> = BTreeSet::new();
+    let mut seq = CircularBuffer::with_rolling_hash(4, CHANGE_BASE, |change: &i8| (change + 9) as u64);
+    let mut seen = vec![false; SEQUENCES];
     let mut prev_secret: u128;
-    for i in 0..nth {
+    for _ in 0..nth {
         prev_secret = cur_secret;
         cur_secret = cur_secret_number(cur_secret);
 
         let change = (cur_secret%10) as i8 - (prev_secret%10) as i8;
 
-        seq = (seq.1, seq.2, seq.3, change as i8);
+        seq.push(change);
 
-        if i < 3 {
+        if !seq.is_full() {
             continue
         }
 
-        if seen.contains(&seq) {
+        let key = seq.rolling_hash().unwrap() as usize;
+        if seen[key] {
             continue
         }
-        seen.insert(seq);
+        seen[key] = true;
 
-        *registry.entry(seq).or_insert(0) += (cur_secret%10) as i32;
+        registry[key] += (cur_secret%10) as i32;
     }
 }
 
 
-pub fn process_part1(input: &str) -> String {
-    parse_input(input)
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    Ok(parse_input(input)?
         .par_iter()
         .map(|num| nth_secret(*num, 2000))
         .sum::<u128>()
-        .to_string()
+        .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {    
-    let res = parse_input(input)
+pub fn process_part2(input: &str) -> ParseResult<String> {    
+    let res = parse_input(input)?
         .par_iter()
-        .map(|initial_secret|{
-            let mut res = HashMap::new();
-
+        .fold(|| vec![0; SEQUENCES], |mut res, initial_secret| {
             prices_diff(*initial_secret, 2000, &mut res);
 
             res
         })
-        .reduce(HashMap::new, |a, b| {
-            let mut res = a.clone();
-            
-            b.iter()
-                .for_each(|(k, v)| {
-                    *res.entry(*k).or_insert(0) += *v
-                });
-            
-            res
+        .reduce(|| vec![0; SEQUENCES], |mut a, b| {
+            a.iter_mut()
+                .zip(b)
+                .for_each(|(total, v)| *total += v);
+
+            a
         })
     ;
 
-    
-
-    res.iter().map(|(k,v)| *v).max().unwrap().to_string()
+    Ok(res.into_iter().max().unwrap().to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "1
-10
-100
-2024";
-    const INPUT2: &str = "1
-2
-3
-2024";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+pub struct Day22;
+
+impl Solution for Day22 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 22;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
-    
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT2));
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day22);
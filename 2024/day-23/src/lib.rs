@@ -1,26 +1,27 @@
-use std::collections::{HashMap, HashSet};
+use aoclib::{Answer, ParseError, ParseResult, Solution};
+use std::collections::HashSet;
 
 use itertools::Itertools;
 
-fn parse_input(input: &str) -> Graph {
+fn parse_input(input: &str) -> ParseResult<Graph<'_>> {
     let mut result = Graph::new();
     
     let edges = input
         .trim()
         .lines()
-        .filter_map(|line| {
-            let edges: Vec<&str> = line.trim().split("-").collect();
-            if edges.len() < 2 {
-                None
-            } else {
-                Some((edges[0], edges[1]))
-            }
+        .map(|line| {
+            let line = line.trim();
+            let (from, to) = line.split_once("-")
+                .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+                .ok_or_else(|| ParseError::at(input, line, "connection like `kh-tc`"))?;
+
+            Ok((from, to))
         })
-        .collect();
+        .collect::<ParseResult<_>>()?;
     
     result.build_graph(&edges);
     
-    result
+    Ok(result)
 }
 
 type Node<'a> = &'a str;
@@ -43,9 +44,8 @@ impl<'a> Graph<'a> {
     }
 
     fn add_unique_node(&mut self, n: Node<'a>) -> usize {
-        let pos = self.node_idx(n);
-        if pos.is_some() {
-            return pos.unwrap();
+        if let Some(pos) = self.node_idx(n) {
+            return pos;
         }
 
         let res = self.nodes.len();
@@ -88,7 +88,7 @@ impl<'a> Graph<'a> {
             .collect()
     }
 
-    fn check_clique_candidate(&self, nodes: &Vec<usize>) -> bool{
+    fn check_clique_candidate(&self, nodes: &[usize]) -> bool{
         nodes
             .iter()
             .combinations(2)
@@ -114,7 +114,7 @@ impl<'a> Graph<'a> {
                     
                     group.extend(candidates);
                     group.sort();
-                    let group = group.into_iter().map(|x|x.to_owned()).collect();
+                    let group: Vec<usize> = group.into_iter().map(|x|x.to_owned()).collect();
 
                     if self.check_clique_candidate(&group) {
                         Some(group)
@@ -137,8 +137,8 @@ impl<'a> Graph<'a> {
     }
 }
 
-pub fn process_part1(input: &str) -> String {
-    let graph = parse_input(input);
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let graph = parse_input(input)?;
 
     let mut res = vec![];
     for (idx, node) in graph.nodes.iter().enumerate() {
@@ -149,22 +149,20 @@ pub fn process_part1(input: &str) -> String {
         res.push(idx);
     }
 
-    graph.cliques(&res, 3)
-        .iter()
-        .count()
-        .to_string()
+    Ok(graph.cliques(&res, 3).len()
+        .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    let graph = parse_input(input);
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let graph = parse_input(input)?;
 
     let nodes = (0..graph.nodes.len()).collect();
 
     let mut max_clique = vec![];
     for i in (1..100).rev() {
         let result = graph.cliques(&nodes, i);
-        if result.len() > 0 {
-            max_clique = result.iter().nth(0).unwrap().to_owned();
+        if !result.is_empty() {
+            max_clique = result.iter().next().unwrap().to_owned();
             break;
         }
     }
@@ -172,53 +170,24 @@ pub fn process_part2(input: &str) -> String {
     let mut password = graph.stringify(&max_clique);
     password.sort();
 
-    password.join(",").to_string()
-
+    Ok(password.join(",").to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "kh-tc
-qp-kh
-de-cg
-ka-co
-yn-aq
-qp-ub
-cg-tb
-vc-aq
-tb-ka
-wh-tc
-yn-cg
-kh-ub
-ta-co
-de-co
-tc-td
-tb-wq
-wh-td
-ta-ka
-td-qp
-aq-cg
-wq-ub
-ub-vc
-de-ta
-wq-aq
-wq-vc
-wh-yn
-ka-de
-kh-ta
-co-tc
-wh-qp
-tb-vc
-td-yn";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+pub struct Day23;
+
+impl Solution for Day23 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 23;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
-    
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day23);
@@ -1,25 +1,24 @@
-use std::{collections::{HashMap, HashSet}, thread::current};
+use aoclib::{Answer, ParseError, ParseResult, Solution};
+use aoclib::parse::{expect, lines, literal, paragraph_break, parse_all, IResult};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{alphanumeric1, digit1, newline},
-    combinator::{map, opt},
-    multi::fold_many1,
-    sequence::{preceded, tuple},
-    IResult,
+    character::complete::{alphanumeric1, one_of},
+    combinator::map,
+    sequence::{preceded, separated_pair, tuple},
 };
 
 type Identifier<'a> = &'a str;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum Expression<'a> {
     And(Identifier<'a>, Identifier<'a>),
     Or(Identifier<'a>, Identifier<'a>),
     Xor(Identifier<'a>, Identifier<'a>),
-    Undefined,
 }
 
 #[derive(Debug)]
@@ -28,72 +27,63 @@ struct Circuit<'a> {
     gates: HashMap<&'a str, Expression<'a>>,
 }
 
-fn value_parser(input: &str) -> IResult<&str, (&str, bool)> {
+fn value_parser(input: &str) -> IResult<'_, (&str, bool)> {
     map(
-        preceded(
-            opt(newline),
-            tuple((
-                alphanumeric1,
-                preceded(tag(": "), digit1),
-            ))
-        ),
-
-        |(id, val): (&str, &str)| {
-            match val {
-                "1" => (id, true),
-                _ => (id, false),
-            }
-        }
+        tuple((
+            alphanumeric1,
+            preceded(literal(": "), one_of("01")),
+        )),
+
+        |(id, val): (&str, char)| (id, val == '1')
     )(input)
 }
 
-fn expression_parser(input: &str) -> IResult<&str, (&str, Expression)> {
+fn expression_parser(input: &str) -> IResult<'_, (&str, Expression<'_>)> {
     use Expression::*;
     map(
-        preceded(
-            opt(newline),
-            tuple((
-                alphanumeric1,
-                preceded(tag(" "), alt((tag("XOR"), tag("OR"), tag("AND")))),
-                preceded(tag(" "), alphanumeric1),
-                preceded(tag(" -> "), alphanumeric1),
-            )),
-        ),
+        tuple((
+            alphanumeric1,
+            preceded(literal(" "), expect("gate `XOR`, `OR` or `AND`", alt((tag("XOR"), tag("OR"), tag("AND"))))),
+            preceded(literal(" "), alphanumeric1),
+            preceded(literal(" -> "), alphanumeric1),
+        )),
         |(left, op, right, res): (&str,&str,&str,&str)| {
             match op {
                 "XOR" => (res, Xor(left, right)),
                 "OR" => (res, Or(left, right)),
-                "AND" => (res, And(left, right)),
-                _ => (res, Undefined),
+                // `AND`, the parser accepts no other gate
+                _ => (res, And(left, right)),
             }
         }
     )(input)
 }
 
-fn empty_parser(input: &str) -> IResult<&str, ()> {
-    fold_many1(newline, ||{}, |_, _| {})(input)
-}
-
-fn parse_input(input: &str) -> Circuit<'_> {
-    let mut values = HashMap::new();
-    let mut gates= HashMap::new();
-
-    let mut input = input;
-    while !input.is_empty() {
-        if let Ok((new_input, (id, expr))) = expression_parser(input) {
-            input = new_input;
-            gates.insert(id, expr);
-        } else if let Ok((new_input, (id, val))) = value_parser(input) {
-            input = new_input;
-            values.insert(id, val);
-        } else if let Ok((new_input, _)) = empty_parser(input) {
-            input = new_input;
-        } else {
-            panic!("Invalid input received: {}", input)
+fn parse_input(input: &str) -> ParseResult<Circuit<'_>> {
+    let (values, gates) = parse_all(
+        input,
+        separated_pair(lines(value_parser), paragraph_break, lines(expression_parser)),
+    )?;
+
+    let circuit = Circuit {
+        values: values.into_iter().collect(),
+        gates: gates.iter().map(|&(id, ref expr)| (id, expr.clone())).collect(),
+    };
+
+    // every gate input has to be driven by an initial value or another gate
+    for (_, expr) in &gates {
+        let (Expression::And(left, right) | Expression::Or(left, right) | Expression::Xor(left, right)) = expr;
+        for wire in [left, right] {
+            if !circuit.values.contains_key(wire) && !circuit.gates.contains_key(wire) {
+                return Err(ParseError::at(input, wire, "wire with an initial value or a gate output"));
+            }
         }
     }
 
-    Circuit { values, gates }
+    if !circuit.gates.keys().chain(circuit.values.keys()).any(|id| id.starts_with('z')) {
+        return Err(ParseError::at(input, &input[input.len()..], "wire starting with `z`"));
+    }
+
+    Ok(circuit)
 }
 
 
@@ -103,60 +93,55 @@ impl<'a> Circuit<'a> {
         use Expression::*;
         let expr = self.gates.get(id).unwrap();
 
-        match expr {
-            Or(l, r)|Xor(l, r)|And(l, r) => {
-                if let (Some(left_val), Some(right_val)) = (self.values.get(r), self.values.get(l)) {
-                    return match expr {
-                        Or(_, _) => {Some(left_val | right_val) },
-                        And(_, _) => {Some(left_val & right_val) },
-                        Xor(_, _) => {Some(left_val ^ right_val) },
-                        _ => {None},
-                    }
-                }
-            },
-            Undefined => panic!("Undefined expression")
+        let (Or(l, r)|Xor(l, r)|And(l, r)) = expr;
+        if let (Some(left_val), Some(right_val)) = (self.values.get(r), self.values.get(l)) {
+            return match expr {
+                Or(_, _) => {Some(left_val | right_val) },
+                And(_, _) => {Some(left_val & right_val) },
+                Xor(_, _) => {Some(left_val ^ right_val) },
+            }
         }
 
         None
     }
 
 
-    pub fn compute_expressions(&mut self) {
+    // gives back a gate that cannot be computed, as it depends on its own output
+    pub fn compute_expressions(&mut self) -> Result<(), &'a str> {
         while !self.gates.is_empty() {
+            let mut progress = false;
             for item in self.gates.keys().cloned().collect::<Vec<&str>>() {
                 if let Some(result) = self.compute_expression(item) {
                     self.gates.remove(item);
 
                     self.values.insert(item, result);
+                    progress = true;
                 }
             }
-            
+
+            if !progress {
+                return Err(self.gates.keys().min().unwrap());
+            }
         }
+
+        Ok(())
     }
 
     pub fn result(&self) -> usize {
-        let res = self.values
-            .iter()
-            .filter_map(|(k, _)| {
+        self.values.keys().filter_map(|k| {
                 if k.starts_with('z') {
                     Some(*k)
                 } else {
                     None
                 }
             })
+            // z00 is the least significant bit
             .sorted()
-            .map(|id| {
-                if *self.values.get(id).unwrap() {
-                    '1'
-                } else {
-                    '0'
-                }
-            })
-            .collect::<String>();
-
-        usize::from_str_radix(&res, 2).unwrap()
+            .rev()
+            .fold(0, |res, id| res << 1 | usize::from(self.values[id]))
     }
 
+    #[allow(unused)]
     fn print_expression(&self, exp: &str, depth: usize, max_depth: usize) {
         use Expression::*;
 
@@ -297,23 +282,23 @@ impl<'a> Circuit<'a> {
     // pub fn check_adder_sum_equation(expr: &str)
 }
 
-pub fn process_part1(input: &str) -> String {
-    let mut circuit = parse_input(input);
-    circuit.compute_expressions();
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let mut circuit = parse_input(input)?;
+    circuit
+        .compute_expressions()
+        .map_err(|gate| ParseError::at(input, gate, "gate that does not depend on its own output"))?;
 
-    circuit.result().to_string()
+    Ok(circuit.result().to_string())
 }
 
 
 
-pub fn process_part2(input: &str) -> String {
-    
-    let mut circuit = parse_input(input);
+pub fn process_part2(input: &str) -> ParseResult<String> {
+    let circuit = parse_input(input)?;
 
     let last_bit = "z45";
 
-    let mut invalid = circuit.gates.iter()
-        .filter_map(|(k, _)| {
+    let mut invalid = circuit.gates.keys().filter_map(|k| {
             if !k.starts_with('z') {
                 None
             } else {
@@ -322,102 +307,47 @@ pub fn process_part2(input: &str) -> String {
         }).collect::<Vec<&str>>();
 
     invalid.sort();
-    invalid.join(",").to_string()
+    Ok(invalid.join(",").to_string())
 }
 
+pub struct Day24;
+
+impl Solution for Day24 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 24;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
+    }
+}
+
+aoclib::expected_tests!(Day24);
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    const INPUT: &str = "x00: 1
-x01: 0
-x02: 1
-x03: 1
-x04: 0
-y00: 1
-y01: 1
-y02: 1
-y03: 1
-y04: 1
-
-ntg XOR fgs -> mjb
-y02 OR x01 -> tnw
-kwq OR kpj -> z05
-x00 OR x03 -> fst
-tgd XOR rvg -> z01
-vdt OR tnw -> bfw
-bfw AND frj -> z10
-ffh OR nrd -> bqk
-y00 AND y03 -> djm
-y03 OR y00 -> psh
-bqk OR frj -> z08
-tnw OR fst -> frj
-gnj AND tgd -> z11
-bfw XOR mjb -> z00
-x03 OR x00 -> vdt
-gnj AND wpb -> z02
-x04 AND y00 -> kjc
-djm OR pbm -> qhw
-nrd AND vdt -> hwm
-kjc AND fst -> rvg
-y04 OR y02 -> fgs
-y01 AND x02 -> pbm
-ntg OR kjc -> kwq
-psh XOR fgs -> tgd
-qhw XOR tgd -> z09
-pbm OR djm -> kpj
-x03 XOR y03 -> ffh
-x00 XOR y04 -> ntg
-bfw OR bqk -> z06
-nrd XOR fgs -> wpb
-frj XOR qhw -> z04
-bqk OR frj -> z07
-y03 OR x01 -> nrd
-hwm AND bqk -> z03
-tgd XOR rvg -> z12
-tnw OR pbm -> gnj";
-
-const INPUT2: &str = "x00: 0
-x01: 1
-x02: 1
-x03: 0
-x04: 1
-y00: 1
-y01: 0
-y02: 1
-y03: 1
-
-x00 AND y00 -> z02
-x01 AND y01 -> z01
-x02 AND y02 -> z00
-x03 AND y03 -> z03";
-
-    const INPUT3: &str = "x00: 0
-x01: 1
-x02: 0
-x03: 1
-x04: 0
-x05: 1
-y00: 0
-y01: 0
-y02: 1
-y03: 1
-y04: 0
-y05: 1
-
-x00 AND y00 -> z05
-x01 AND y01 -> z02
-x02 AND y02 -> z01
-x03 AND y03 -> z03
-x04 AND y04 -> z04
-x05 AND y05 -> z00";
 
     #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
-    }
-    
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT3));
+    fn test_invalid_input() {
+        let err = process_part1("x00: 1\nx01: 2\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 6: expected one of the allowed characters, found `2`");
+
+        let err = process_part1("x00: 1\n\nx00 NAND y00 -> z00\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 5));
+
+        let err = process_part1("x00: 1\n\nx00 AND q01 -> z00\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 9: expected wire with an initial value or a gate output, found `q01`");
+
+        let err = process_part1("x00: 1\n\nx00 AND x00 -> a00\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 4, column 1: expected wire starting with `z`, found end of input");
+
+        let err = process_part1("x00: 1\n\nx00 AND b00 -> a00\na00 OR x00 -> b00\nx00 OR x00 -> z00\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 16: expected gate that does not depend on its own output, found `a00`");
     }
 }
@@ -1,4 +1,5 @@
-use itertools::Itertools;
+use aoclib::{Answer, ParseError, ParseResult, Solution};
+
 
 type Key = (i8, i8, i8, i8, i8);
 type Lock = (i8, i8, i8, i8, i8);
@@ -13,9 +14,21 @@ fn key_fit_lock(lock: &Lock, key: &Key) -> bool {
         && lock.3 + key.3 <= 5 && lock.4 + key.4 <= 5
 }
 
-fn parse_part(input: &str) -> Item {
-    const EMPTY: u8 = '.' as u8;
-    const CODE: u8 = '#' as u8;
+fn parse_part(full_input: &str, input: &str) -> ParseResult<Item> {
+    const EMPTY: u8 = b'.';
+    const CODE: u8 = b'#';
+
+    for line in input.lines() {
+        if let Some(pos) = line.find(|c| c != '.' && c != '#') {
+            return Err(ParseError::at(full_input, &line[pos..], "`#` or `.`"));
+        }
+        if line.len() != 5 {
+            return Err(ParseError::at(full_input, &line[line.len().min(5)..], "row of 5 pins"));
+        }
+    }
+    if input.lines().count() != 7 {
+        return Err(ParseError::at(full_input, input, "schematic of 7 rows"));
+    }
 
     let normalized_input = input.as_bytes();
 
@@ -42,7 +55,7 @@ fn parse_part(input: &str) -> Item {
         res
     };
 
-    if normalized_input[0] == EMPTY {
+    Ok(if normalized_input[0] == EMPTY {
         Item::Key((
             5 - get_height(0, EMPTY),
             5 - get_height(1, EMPTY),
@@ -58,15 +71,15 @@ fn parse_part(input: &str) -> Item {
             get_height(3, CODE),
             get_height(4, CODE),
         ))
-    }
+    })
 }
 
-fn parse_input(input: &str) -> (Vec<Lock>, Vec<Key>) {
+fn parse_input(input: &str) -> ParseResult<(Vec<Lock>, Vec<Key>)> {
     input
         .trim()
         .split("\n\n")
-        .fold((Vec::new(), Vec::new()), |(mut locks_acc, mut keys_acc), lines| {
-            match parse_part(lines) {
+        .try_fold((Vec::new(), Vec::new()), |(mut locks_acc, mut keys_acc), lines| {
+            match parse_part(input, lines)? {
                 Item::Key(k) => {
                     keys_acc.push(k);
                 },
@@ -75,16 +88,15 @@ fn parse_input(input: &str) -> (Vec<Lock>, Vec<Key>) {
                 }
             }
 
-            (locks_acc, keys_acc)
+            Ok((locks_acc, keys_acc))
         })
-
 }
 
 
-pub fn process_part1(input: &str) -> String {
-    let (locks, keys) = parse_input(input);
+pub fn process_part1(input: &str) -> ParseResult<String> {
+    let (locks, keys) = parse_input(input)?;
 
-    locks
+    Ok(locks
         .iter()
         .map(|lock| {
             keys
@@ -95,63 +107,28 @@ pub fn process_part1(input: &str) -> String {
                 .sum::<i32>()
         })
         .sum::<i32>()
-        .to_string()
+        .to_string())
 }
 
-pub fn process_part2(input: &str) -> String {
-    "Free star here!".to_string()
+pub fn process_part2(_input: &str) -> ParseResult<String> {
+    Ok("Free star here!".to_string())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const INPUT: &str = "#####
-.####
-.####
-.####
-.#.#.
-.#...
-.....
-
-#####
-##.##
-.#.##
-...##
-...#.
-...#.
-.....
-
-.....
-#....
-#....
-#...#
-#.#.#
-#.###
-#####
-
-.....
-.....
-#.#..
-###..
-###.#
-###.#
-#####
-
-.....
-.....
-.....
-#....
-#.#..
-#.#.#
-#####";
-
-    #[test]
-    fn test_process_part1() {
-        println!("{}", process_part1(INPUT));
+pub struct Day25;
+
+impl Solution for Day25 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 25;
+
+    type Params = ();
+
+    fn part1(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part1(input).map(Answer::from)
     }
-    
-    #[test]
-    fn test_process_part2() {
-        println!("{}", process_part2(INPUT));
+
+    fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+        process_part2(input).map(Answer::from)
     }
-}
\ No newline at end of file
+}
+
+aoclib::expected_tests!(Day25);
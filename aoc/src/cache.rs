@@ -0,0 +1,110 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{client::Client, config::Config};
+
+/// Downloaded puzzle inputs, stored as `<year>/day-<day>.txt` in the cache directory.
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(dir: &Path) -> Self {
+        InputCache { dir: dir.to_path_buf() }
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir.join(year.to_string()).join(format!("day-{:02}.txt", day))
+    }
+
+    pub fn read(&self, year: u16, day: u8) -> Option<String> {
+        fs::read_to_string(self.path(year, day)).ok()
+    }
+
+    /// Path of the cached input, downloading it only when it is not cached yet.
+    /// The flag tells whether the input was downloaded.
+    pub fn fetch(&self, year: u16, day: u8, config: &Config) -> Result<(PathBuf, bool), String> {
+        let path = self.path(year, day);
+        if path.exists() {
+            return Ok((path, false));
+        }
+
+        let input = Client::new(&config.base_url, config.session()?).input(year, day)?;
+        self.store(&path, &input)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+
+        Ok((path, true))
+    }
+
+    // written under another name first, so an interrupted write is never taken for an input
+    fn store(&self, path: &Path, input: &str) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let partial = path.with_extension("part");
+        fs::write(&partial, input)?;
+        fs::rename(&partial, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub_server::StubServer;
+
+    fn config(server: &StubServer, cache_dir: &Path) -> Config {
+        Config {
+            session: Some("secret".to_string()),
+            base_url: server.url(),
+            cache_dir: cache_dir.to_path_buf(),
+            data_dir: cache_dir.to_path_buf(),
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_once() {
+        let server = StubServer::start(vec![(200, "1,2\n3,4\n")]);
+        let dir = temp_dir("fetch-once");
+        let cache = InputCache::new(&dir);
+        let config = config(&server, &dir);
+
+        let (path, downloaded) = cache.fetch(2024, 18, &config).unwrap();
+        assert!(downloaded);
+        assert_eq!(path, dir.join("2024").join("day-18.txt"));
+        assert_eq!(cache.read(2024, 18).as_deref(), Some("1,2\n3,4\n"));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2024/day/18/input "));
+        assert!(requests[0].contains("session=secret"));
+
+        // the stub is gone, so a second download would fail
+        let (_, downloaded) = cache.fetch(2024, 18, &config).unwrap();
+        assert!(!downloaded);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_failed_fetch_is_not_cached() {
+        let server = StubServer::start(vec![(400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.")]);
+        let dir = temp_dir("fetch-failed");
+        let cache = InputCache::new(&dir);
+
+        let err = cache.fetch(2024, 1, &config(&server, &dir)).unwrap_err();
+        assert!(err.contains("returned 400: Puzzle inputs differ by user."), "{}", err);
+        assert_eq!(cache.read(2024, 1), None);
+
+        let no_session = Config { session: None, ..config(&server, &dir) };
+        assert!(cache.fetch(2024, 1, &no_session).unwrap_err().contains("no session cookie"));
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+const USER_AGENT: &str = "github.com/daniel1302/advent-of-code aoc runner";
+
+/// Advent of Code website, logged in with a session cookie.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Puzzle input of the logged in user.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = self.agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| describe_error(&url, e))?;
+
+        response.into_string().map_err(|e| format!("{}: {}", url, e))
+    }
+
+    /// Posts the answer of a part, returns the page telling the verdict.
+    pub fn answer(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<String, String> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let response = self.agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| describe_error(&url, e))?;
+
+        response.into_string().map_err(|e| format!("{}: {}", url, e))
+    }
+}
+
+fn describe_error(url: &str, err: ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            format!("{} returned {}: {}", url, code, body.trim())
+        }
+        ureq::Error::Transport(e) => format!("{}: {}", url, e),
+    }
+}
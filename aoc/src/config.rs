@@ -0,0 +1,123 @@
+use std::{env, fs, path::PathBuf};
+
+use serde::Deserialize;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings of the commands talking to the Advent of Code website.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Value of the `session` cookie of a logged in browser.
+    pub session: Option<String>,
+    pub base_url: String,
+    /// Per-user directory keeping downloaded inputs.
+    pub cache_dir: PathBuf,
+    /// Per-user directory keeping the ledger of submitted answers.
+    pub data_dir: PathBuf,
+}
+
+// every field may be left out of the config file
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    session: Option<String>,
+    base_url: Option<String>,
+    cache_dir: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+}
+
+impl Config {
+    /// Reads `aoc/config.toml` from the user config directory, or the file named by
+    /// `AOC_CONFIG`. The `AOC_SESSION`, `AOC_BASE_URL`, `AOC_CACHE_DIR` and `AOC_DATA_DIR`
+    /// variables take precedence over the file.
+    pub fn load() -> Result<Config, String> {
+        let path = env::var_os("AOC_CONFIG")
+            .map(PathBuf::from)
+            .or_else(|| dirs::config_dir().map(|dir| dir.join("aoc").join("config.toml")));
+
+        let file = match path {
+            Some(path) if path.exists() => Some(
+                fs::read_to_string(&path)
+                    .map_err(|e| format!("cannot read {}: {}", path.display(), e))?
+            ),
+            _ => None,
+        };
+
+        Config::from_sources(file.as_deref(), |name| env::var(name).ok())
+    }
+
+    /// Builds the config out of the config file contents and the environment.
+    pub fn from_sources<F>(file: Option<&str>, var: F) -> Result<Config, String>
+        where F: Fn(&str) -> Option<String>
+    {
+        let file: ConfigFile = match file {
+            Some(contents) => toml::from_str(contents).map_err(|e| format!("config: {}", e))?,
+            None => ConfigFile::default(),
+        };
+
+        let session = var("AOC_SESSION")
+            .or(file.session)
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty());
+
+        let base_url = var("AOC_BASE_URL")
+            .or(file.base_url)
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        let cache_dir = var("AOC_CACHE_DIR")
+            .map(PathBuf::from)
+            .or(file.cache_dir)
+            .or_else(|| dirs::cache_dir().map(|dir| dir.join("aoc")))
+            .ok_or("no cache directory, set AOC_CACHE_DIR")?;
+
+        let data_dir = var("AOC_DATA_DIR")
+            .map(PathBuf::from)
+            .or(file.data_dir)
+            .or_else(|| dirs::data_dir().map(|dir| dir.join("aoc")))
+            .ok_or("no data directory, set AOC_DATA_DIR")?;
+
+        Ok(Config { session, base_url, cache_dir, data_dir })
+    }
+
+    pub fn session(&self) -> Result<&str, String> {
+        self.session
+            .as_deref()
+            .ok_or_else(|| "no session cookie, set AOC_SESSION or `session` in the config file".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_overrides_file() {
+        let file = "session = \"abc\"\nbase_url = \"http://localhost:8080/\"\ncache_dir = \"/tmp/aoc\"\ndata_dir = \"/tmp/aoc-data\"\n";
+
+        let config = Config::from_sources(Some(file), |_| None).unwrap();
+        assert_eq!(config, Config {
+            session: Some("abc".to_string()),
+            base_url: "http://localhost:8080".to_string(),
+            cache_dir: "/tmp/aoc".into(),
+            data_dir: "/tmp/aoc-data".into(),
+        });
+
+        let config = Config::from_sources(Some(file), |name| {
+            (name == "AOC_SESSION").then(|| "from-env\n".to_string())
+        }).unwrap();
+        assert_eq!(config.session(), Ok("from-env"));
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::from_sources(None, |name| {
+            name.ends_with("_DIR").then(|| "/tmp/aoc".to_string())
+        }).unwrap();
+
+        assert_eq!(config.base_url, DEFAULT_BASE_URL);
+        assert!(config.session().is_err());
+        assert!(Config::from_sources(Some("token = 1"), |_| None).is_err());
+    }
+}
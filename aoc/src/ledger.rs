@@ -0,0 +1,204 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+/// What the website said about a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+/// Submitted answer of a puzzle part.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Result of checking an answer against the earlier attempts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// Nothing rules the answer out, it may be posted.
+    Allowed,
+    /// The same answer was accepted before.
+    Accepted,
+    /// The answer cannot be right, or the website would not take it yet.
+    Refused(String),
+}
+
+// the website throttles answers per puzzle, both parts share the wait
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Wait {
+    year: u16,
+    day: u8,
+    /// Unix time before which the website refuses answers to the puzzle.
+    until: u64,
+}
+
+/// Answers submitted so far, with their verdicts.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ledger {
+    attempts: Vec<Attempt>,
+    waits: Vec<Wait>,
+}
+
+impl Ledger {
+    /// Reads the ledger at `path`, a missing file is an empty ledger.
+    pub fn load(path: &Path) -> Result<Ledger, String> {
+        if !path.exists() {
+            return Ok(Ledger::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        toml::from_str(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = toml::to_string(self).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+        }
+        fs::write(path, contents).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+    }
+
+    pub fn attempts(&self, year: u16, day: u8, part: u8) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| (a.year, a.day, a.part) == (year, day, part))
+    }
+
+    /// Whether `answer` is worth posting at unix time `now`. Numeric answers are
+    /// also compared with the ones found too high or too low.
+    pub fn check(&self, year: u16, day: u8, part: u8, answer: &str, now: u64) -> Check {
+        let attempts: Vec<_> = self.attempts(year, day, part).collect();
+
+        if let Some(correct) = attempts.iter().find(|a| a.verdict == Verdict::Correct) {
+            return if correct.answer == answer {
+                Check::Accepted
+            } else {
+                Check::Refused(format!("part {} was already solved with {}", part, correct.answer))
+            };
+        }
+
+        if let Some(known) = attempts.iter().find(|a| a.answer == answer) {
+            return Check::Refused(format!("{} was already submitted and found {}", answer, describe(known.verdict)));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let ruled_out = attempts.iter().find(|a| {
+                let Ok(earlier) = a.answer.parse::<i128>() else {
+                    return false;
+                };
+                match a.verdict {
+                    Verdict::TooHigh => value >= earlier,
+                    Verdict::TooLow => value <= earlier,
+                    _ => false,
+                }
+            });
+            if let Some(earlier) = ruled_out {
+                return Check::Refused(format!(
+                    "{} cannot be right, {} was {}",
+                    answer, earlier.answer, describe(earlier.verdict)
+                ));
+            }
+        }
+
+        let wait = self.waits.iter().find(|w| (w.year, w.day) == (year, day));
+        match wait {
+            Some(wait) if wait.until > now => Check::Refused(format!("wait {}s before submitting again", wait.until - now)),
+            _ => Check::Allowed,
+        }
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    /// Refuses answers to the puzzle until unix time `until`.
+    pub fn wait_until(&mut self, year: u16, day: u8, until: u64) {
+        match self.waits.iter_mut().find(|w| (w.year, w.day) == (year, day)) {
+            Some(wait) => wait.until = until,
+            None => self.waits.push(Wait { year, day, until }),
+        }
+    }
+}
+
+pub fn describe(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => "correct",
+        Verdict::TooHigh => "too high",
+        Verdict::TooLow => "too low",
+        Verdict::Wrong => "wrong",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(part: u8, answer: &str, verdict: Verdict) -> Attempt {
+        Attempt { year: 2024, day: 7, part, answer: answer.to_string(), verdict }
+    }
+
+    #[test]
+    fn test_check_against_verdicts() {
+        let mut ledger = Ledger::default();
+        ledger.record(attempt(1, "500", Verdict::TooHigh));
+        ledger.record(attempt(1, "100", Verdict::TooLow));
+        ledger.record(attempt(1, "250", Verdict::Wrong));
+
+        assert_eq!(ledger.check(2024, 7, 1, "300", 0), Check::Allowed);
+        assert!(matches!(ledger.check(2024, 7, 1, "250", 0), Check::Refused(_)));
+        assert_eq!(
+            ledger.check(2024, 7, 1, "600", 0),
+            Check::Refused("600 cannot be right, 500 was too high".to_string())
+        );
+        assert!(matches!(ledger.check(2024, 7, 1, "100", 0), Check::Refused(_)));
+        // other parts and days are not affected
+        assert_eq!(ledger.check(2024, 7, 2, "600", 0), Check::Allowed);
+        assert_eq!(ledger.check(2024, 8, 1, "600", 0), Check::Allowed);
+
+        ledger.record(attempt(1, "300", Verdict::Correct));
+        assert_eq!(ledger.check(2024, 7, 1, "300", 0), Check::Accepted);
+        assert!(matches!(ledger.check(2024, 7, 1, "301", 0), Check::Refused(_)));
+    }
+
+    #[test]
+    fn test_wait() {
+        let mut ledger = Ledger::default();
+        ledger.wait_until(2024, 1, 100);
+
+        assert_eq!(ledger.check(2024, 1, 1, "1", 40), Check::Refused("wait 60s before submitting again".to_string()));
+        assert_eq!(ledger.check(2024, 1, 2, "1", 40), Check::Refused("wait 60s before submitting again".to_string()));
+        assert_eq!(ledger.check(2024, 1, 1, "1", 100), Check::Allowed);
+        // other puzzles are not throttled
+        assert_eq!(ledger.check(2024, 2, 1, "1", 40), Check::Allowed);
+
+        ledger.wait_until(2024, 1, 50);
+        assert_eq!(ledger.check(2024, 1, 1, "1", 50), Check::Allowed);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("aoc-ledger-{}.toml", std::process::id()));
+        let mut ledger = Ledger::default();
+        ledger.record(attempt(2, "abc", Verdict::Wrong));
+        ledger.wait_until(2024, 7, 1234);
+
+        ledger.save(&path).unwrap();
+        assert_eq!(Ledger::load(&path), Ok(ledger));
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(Ledger::load(&path), Ok(Ledger::default()));
+    }
+}
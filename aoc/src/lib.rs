@@ -0,0 +1,9 @@
+pub mod cache;
+pub mod client;
+pub mod config;
+pub mod ledger;
+pub mod registry;
+pub mod submit;
+
+#[cfg(test)]
+mod stub_server;
@@ -0,0 +1,205 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use aoc::{
+    cache::InputCache,
+    config::Config,
+    ledger::{self, Verdict},
+    registry,
+    submit::{self, Outcome},
+};
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code solutions runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution of a single puzzle
+    Run {
+        year: u16,
+        day: u8,
+
+        /// Part to run, both parts are run when omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Puzzle input, defaults to `<year>/day-<day>/input.txt` in the workspace,
+        /// then to the input downloaded by `fetch`
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Step through the simulation of a puzzle in the terminal
+    View {
+        year: u16,
+        day: u8,
+
+        /// Part whose simulation is shown, when the parts differ
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Puzzle input, defaults to `<year>/day-<day>/input.txt` in the workspace,
+        /// then to the input downloaded by `fetch`
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// Download the puzzle input into the cache, unless it is cached already
+    Fetch {
+        year: u16,
+        day: u8,
+    },
+    /// Solve a part and post the answer, unless earlier verdicts rule it out
+    Submit {
+        year: u16,
+        day: u8,
+
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Puzzle input, defaults to `<year>/day-<day>/input.txt` in the workspace,
+        /// then to the input downloaded by `fetch`
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+    /// List every puzzle with a solution
+    List,
+}
+
+fn default_input_path(year: u16, day: u8) -> PathBuf {
+    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+
+    workspace_root
+        .join(year.to_string())
+        .join(format!("day-{:02}", day))
+        .join("input.txt")
+}
+
+fn read_input(year: u16, day: u8, input: Option<PathBuf>) -> Result<(PathBuf, String), String> {
+    let input_path = input.unwrap_or_else(|| {
+        let workspace_input = default_input_path(year, day);
+        if workspace_input.exists() {
+            return workspace_input;
+        }
+
+        // fall back to the downloaded input, if there is one
+        Config::load()
+            .map(|config| InputCache::new(&config.cache_dir).path(year, day))
+            .ok()
+            .filter(|cached| cached.exists())
+            .unwrap_or(workspace_input)
+    });
+    let input = fs::read_to_string(&input_path)
+        .map_err(|e| format!("cannot read {}: {}", input_path.display(), e))?;
+
+    Ok((input_path, input))
+}
+
+fn run(year: u16, day: u8, part: Option<u8>, input: Option<PathBuf>) -> Result<(), String> {
+    let puzzle = registry::find(year, day)
+        .ok_or_else(|| format!("no solution for {} day {}", year, day))?;
+
+    let (input_path, input) = read_input(year, day, input)?;
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    for part in parts {
+        let answer = puzzle.run(part, &input)
+            .ok_or_else(|| format!("no part {} in {} day {}", part, year, day))?
+            .map_err(|e| format!("{}: {}", input_path.display(), e))?;
+
+        println!("{} day {} part {}: {}", year, day, part, answer);
+    }
+
+    Ok(())
+}
+
+fn view(year: u16, day: u8, part: u8, input: Option<PathBuf>) -> Result<(), String> {
+    let (input_path, input) = read_input(year, day, input)?;
+
+    registry::view(year, day, part, &input)
+        .ok_or_else(|| format!("no simulation for {} day {}", year, day))?
+        .map_err(|e| format!("{}: {}", input_path.display(), e))?
+        .map_err(|e| format!("terminal: {}", e))
+}
+
+fn fetch(year: u16, day: u8) -> Result<(), String> {
+    let config = Config::load()?;
+    let (path, downloaded) = InputCache::new(&config.cache_dir).fetch(year, day, &config)?;
+
+    if downloaded {
+        println!("downloaded {} day {} input to {}", year, day, path.display());
+    } else {
+        println!("{} day {} input already cached in {}", year, day, path.display());
+    }
+
+    Ok(())
+}
+
+fn submit(year: u16, day: u8, part: u8, input: Option<PathBuf>) -> Result<(), String> {
+    let puzzle = registry::find(year, day)
+        .ok_or_else(|| format!("no solution for {} day {}", year, day))?;
+
+    let (input_path, input) = read_input(year, day, input)?;
+    let answer = puzzle.run(part, &input)
+        .ok_or_else(|| format!("no part {} in {} day {}", part, year, day))?
+        .map_err(|e| format!("{}: {}", input_path.display(), e))?
+        .to_string();
+
+    let config = Config::load()?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("clock: {}", e))?
+        .as_secs();
+
+    match submit::submit(&config, year, day, part, &answer, now)? {
+        Outcome::Judged(Verdict::Correct) => println!("{} day {} part {}: {} is correct", year, day, part, answer),
+        Outcome::Judged(verdict) => {
+            return Err(format!("{} day {} part {}: {} is {}", year, day, part, answer, ledger::describe(verdict)));
+        },
+        Outcome::AlreadyAccepted => println!("{} day {} part {}: {} was accepted before", year, day, part, answer),
+        Outcome::TooSoon(wait) => return Err(format!("answered too recently, wait {}s before submitting again", wait)),
+        Outcome::WrongLevel => return Err(format!("part {} of {} day {} is locked or already solved", part, year, day)),
+        Outcome::Unknown(text) => return Err(format!("unexpected answer page: {}", text)),
+    }
+
+    Ok(())
+}
+
+fn list() -> Result<(), String> {
+    for puzzle in registry::PUZZLES {
+        println!("{} day {}", puzzle.year, puzzle.day);
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Run { year, day, part, input } => run(year, day, part, input),
+        Command::View { year, day, part, input } => view(year, day, part, input),
+        Command::Fetch { year, day } => fetch(year, day),
+        Command::Submit { year, day, part, input } => submit(year, day, part, input),
+        Command::List => list(),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::io;
+
+use aoclib::{viewer, ParseResult, Puzzle};
+
+/// Every puzzle solved in the workspace, ordered by `(year, day)`.
+pub static PUZZLES: &[Puzzle] = &[
+    Puzzle::of::<aoc_2022_day_01::Day01>(),
+    Puzzle::of::<aoc_2022_day_02::Day02>(),
+    Puzzle::of::<aoc_2022_day_03::Day03>(),
+    Puzzle::of::<aoc_2022_day_04::Day04>(),
+    Puzzle::of::<aoc_2022_day_05::Day05>(),
+    Puzzle::of::<aoc_2022_day_06::Day06>(),
+    Puzzle::of::<aoc_2022_day_07::Day07>(),
+    Puzzle::of::<aoc_2022_day_08::Day08>(),
+    Puzzle::of::<aoc_2022_day_09::Day09>(),
+    Puzzle::of::<aoc_2022_day_10::Day10>(),
+    Puzzle::of::<aoc_2022_day_11::Day11>(),
+    Puzzle::of::<aoc_2024_day_01::Day01>(),
+    Puzzle::of::<aoc_2024_day_02::Day02>(),
+    Puzzle::of::<aoc_2024_day_03::Day03>(),
+    Puzzle::of::<aoc_2024_day_04::Day04>(),
+    Puzzle::of::<aoc_2024_day_05::Day05>(),
+    Puzzle::of::<aoc_2024_day_06::Day06>(),
+    Puzzle::of::<aoc_2024_day_07::Day07>(),
+    Puzzle::of::<aoc_2024_day_08::Day08>(),
+    Puzzle::of::<aoc_2024_day_09::Day09>(),
+    Puzzle::of::<aoc_2024_day_10::Day10>(),
+    Puzzle::of::<aoc_2024_day_11::Day11>(),
+    Puzzle::of::<aoc_2024_day_12::Day12>(),
+    Puzzle::of::<aoc_2024_day_13::Day13>(),
+    Puzzle::of::<aoc_2024_day_14::Day14>(),
+    Puzzle::of::<aoc_2024_day_15::Day15>(),
+    Puzzle::of::<aoc_2024_day_17::Day17>(),
+    Puzzle::of::<aoc_2024_day_18::Day18>(),
+    Puzzle::of::<aoc_2024_day_19::Day19>(),
+    Puzzle::of::<aoc_2024_day_20::Day20>(),
+    Puzzle::of::<aoc_2024_day_21::Day21>(),
+    Puzzle::of::<aoc_2024_day_22::Day22>(),
+    Puzzle::of::<aoc_2024_day_23::Day23>(),
+    Puzzle::of::<aoc_2024_day_24::Day24>(),
+    Puzzle::of::<aoc_2024_day_25::Day25>(),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Puzzle> {
+    PUZZLES
+        .iter()
+        .find(|puzzle| puzzle.year == year && puzzle.day == day)
+}
+
+/// Opens the step-through viewer of a puzzle simulation, `None` when the day has none.
+/// Day 15 of 2024 watches the wide warehouse for part 2.
+pub fn view(year: u16, day: u8, part: u8, input: &str) -> Option<ParseResult<io::Result<()>>> {
+    let result = match (year, day) {
+        (2024, 6) => aoc_2024_day_06::GuardSimulator::parse(input).map(viewer::run),
+        (2024, 14) => aoc_2024_day_14::RobotsSimulation::parse(input, &Default::default()).map(viewer::run),
+        (2024, 15) => aoc_2024_day_15::parse_input(input, part == 2).map(viewer::run),
+        _ => return None,
+    };
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzles_are_sorted_and_unique() {
+        let keys: Vec<(u16, u8)> = PUZZLES
+            .iter()
+            .map(|puzzle| (puzzle.year, puzzle.day))
+            .collect();
+
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_find() {
+        assert!(find(2024, 18).is_some());
+        assert!(find(2024, 16).is_none());
+        assert!(find(2023, 1).is_none());
+    }
+
+    #[test]
+    fn test_view_without_simulation() {
+        assert!(view(2024, 1, 1, "").is_none());
+        // invalid input fails before the terminal is touched
+        assert!(matches!(view(2024, 6, 1, "..x\n"), Some(Err(_))));
+    }
+}
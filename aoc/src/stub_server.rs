@@ -0,0 +1,82 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// Local stand-in for the website, answering requests with canned responses in order.
+/// Once they run out the server stops, so any further request fails.
+pub struct StubServer {
+    url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl StubServer {
+    pub fn start(responses: Vec<(u16, &'static str)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let recorded = Arc::clone(&requests);
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+
+                let mut reader = BufReader::new(stream);
+                let request = read_request(&mut reader);
+                recorded.lock().unwrap().push(request);
+
+                let response = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = reader.get_mut().write_all(response.as_bytes());
+            }
+        });
+
+        StubServer { url, requests }
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// Raw requests received so far, the body included.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> String {
+    let mut request = String::new();
+    let mut content_length = 0;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+
+        request.push_str(&line);
+        if line == "\r\n" {
+            break;
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    if reader.read_exact(&mut body).is_ok() {
+        request.push_str(&String::from_utf8_lossy(&body));
+    }
+
+    request
+}
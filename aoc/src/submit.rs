@@ -0,0 +1,233 @@
+use crate::{
+    client::Client,
+    config::Config,
+    ledger::{Attempt, Check, Ledger, Verdict},
+};
+
+// longest page text shown for an unknown page
+const MAX_PAGE_TEXT: usize = 300;
+
+/// What happened to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The website judged the answer, the verdict is in the ledger.
+    Judged(Verdict),
+    /// The same answer was accepted before, nothing was posted.
+    AlreadyAccepted,
+    /// The website did not judge the answer, it has to wait this many seconds.
+    TooSoon(u64),
+    /// The part is still locked, or was solved outside of the ledger.
+    WrongLevel,
+    /// Any other page, like the one of a logged out user, with its text.
+    Unknown(String),
+}
+
+/// Posts `answer` unless the ledger at `config.data_dir` rules it out, then records
+/// the verdict and any wait the website asks for. `now` is the unix time.
+pub fn submit(config: &Config, year: u16, day: u8, part: u8, answer: &str, now: u64) -> Result<Outcome, String> {
+    let path = config.data_dir.join("answers.toml");
+    let mut ledger = Ledger::load(&path)?;
+
+    match ledger.check(year, day, part, answer, now) {
+        Check::Allowed => {},
+        Check::Accepted => return Ok(Outcome::AlreadyAccepted),
+        Check::Refused(reason) => return Err(reason),
+    }
+
+    let page = Client::new(&config.base_url, config.session()?).answer(year, day, part, answer)?;
+    let outcome = parse_response(&page);
+
+    if let Outcome::Judged(verdict) = outcome {
+        ledger.record(Attempt { year, day, part, answer: answer.to_string(), verdict });
+    }
+    if let Some(wait) = wait_seconds(&page) {
+        ledger.wait_until(year, day, now + wait);
+    }
+    ledger.save(&path)?;
+
+    Ok(outcome)
+}
+
+/// Reads the verdict out of the page returned for an answer.
+pub fn parse_response(page: &str) -> Outcome {
+    if page.contains("That's the right answer") {
+        Outcome::Judged(Verdict::Correct)
+    } else if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            Outcome::Judged(Verdict::TooHigh)
+        } else if page.contains("your answer is too low") {
+            Outcome::Judged(Verdict::TooLow)
+        } else {
+            Outcome::Judged(Verdict::Wrong)
+        }
+    } else if page.contains("You gave an answer too recently") {
+        Outcome::TooSoon(wait_seconds(page).unwrap_or(60))
+    } else if page.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown(page_text(page))
+    }
+}
+
+// `You have 1m 4s left to wait.` after answering too soon, or
+// `Please wait one minute before trying again.` after a wrong answer
+fn wait_seconds(page: &str) -> Option<u64> {
+    if let Some(left) = sentence(page, "You have ", " left to wait") {
+        return left.split_whitespace().try_fold(0, |total, amount| {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "h" => Some(total + number * 3600),
+                "m" => Some(total + number * 60),
+                "s" => Some(total + number),
+                _ => None,
+            }
+        });
+    }
+
+    let delay = sentence(page, "Please wait ", " before trying again")?;
+    let (amount, unit) = delay.split_once(' ')?;
+    let amount = match amount {
+        "one" | "a" => 1,
+        number => number.parse::<u64>().ok()?,
+    };
+
+    match unit {
+        "minute" | "minutes" => Some(amount * 60),
+        "second" | "seconds" => Some(amount),
+        _ => None,
+    }
+}
+
+// text between `start` and `end` within one sentence of the page
+fn sentence<'a>(page: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    page.match_indices(start).find_map(|(pos, _)| {
+        let rest = &page[pos + start.len()..];
+        let inner = &rest[..rest.find(end)?];
+
+        (!inner.contains(['.', '<'])).then_some(inner)
+    })
+}
+
+// page without its markup, shortened to `MAX_PAGE_TEXT` characters
+fn page_text(page: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in page.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => { in_tag = false; text.push(' '); },
+            c if !in_tag => text.push(c),
+            _ => {},
+        }
+    }
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > MAX_PAGE_TEXT {
+        format!("{}...", text.chars().take(MAX_PAGE_TEXT).collect::<String>())
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::*;
+    use crate::stub_server::StubServer;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2024/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2024/day/7\">[Return to Day 7]</a></p></article>";
+    const CORRECT: &str = "<article><p>That's the right answer!  You are one gold star closer to finding the Chief Historian. <a href=\"/2024/day/7#part2\">[Continue to Part Two]</a></p></article>";
+    const TOO_RECENTLY: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 4s left to wait. <a href=\"/2024/day/7\">[Return to Day 7]</a></p></article>";
+    const WRONG_LEVEL: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2024/day/7\">[Return to Day 7]</a></p></article>";
+
+    fn config(server: &StubServer, name: &str) -> Config {
+        let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        Config {
+            session: Some("secret".to_string()),
+            base_url: server.url(),
+            cache_dir: dir.clone(),
+            data_dir: dir,
+        }
+    }
+
+    fn ledger_path(config: &Config) -> PathBuf {
+        config.data_dir.join("answers.toml")
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(parse_response(TOO_HIGH), Outcome::Judged(Verdict::TooHigh));
+        assert_eq!(parse_response(CORRECT), Outcome::Judged(Verdict::Correct));
+        assert_eq!(parse_response(TOO_RECENTLY), Outcome::TooSoon(64));
+        assert_eq!(parse_response(WRONG_LEVEL), Outcome::WrongLevel);
+        assert_eq!(
+            parse_response("<html><body>\n<p>Puzzle inputs differ by user.  Please <a href=\"/auth/login\">log in</a>.</p></body></html>"),
+            Outcome::Unknown("Puzzle inputs differ by user. Please log in .".to_string())
+        );
+
+        assert_eq!(wait_seconds(TOO_HIGH), Some(60));
+        assert_eq!(wait_seconds("Please wait 5 minutes before trying again."), Some(300));
+        assert_eq!(wait_seconds(CORRECT), None);
+        // only the sentences telling the wait count
+        assert_eq!(wait_seconds("<p>You have to wait after submitting an answer. Please wait.</p>"), None);
+        assert_eq!(wait_seconds("<p>Did you wait 5 minutes? You have 2 stars.</p>"), None);
+        assert_eq!(wait_seconds("You have to wait. You have 30s left to wait."), Some(30));
+    }
+
+    #[test]
+    fn test_submit_follows_the_ledger() {
+        let server = StubServer::start(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let config = config(&server, "submit");
+
+        assert_eq!(submit(&config, 2024, 7, 1, "500", 0), Ok(Outcome::Judged(Verdict::TooHigh)));
+        // refused locally, without asking the website
+        assert_eq!(submit(&config, 2024, 7, 1, "400", 30), Err("wait 30s before submitting again".to_string()));
+        assert!(submit(&config, 2024, 7, 1, "600", 100).unwrap_err().contains("500 was too high"));
+
+        assert_eq!(submit(&config, 2024, 7, 1, "400", 100), Ok(Outcome::Judged(Verdict::Correct)));
+        assert_eq!(submit(&config, 2024, 7, 1, "400", 200), Ok(Outcome::AlreadyAccepted));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("POST /2024/day/7/answer "));
+        assert!(requests[0].contains("session=secret"));
+        assert!(requests[1].ends_with("level=1&answer=400"));
+
+        let ledger = Ledger::load(&ledger_path(&config)).unwrap();
+        assert_eq!(ledger.attempts(2024, 7, 1).count(), 2);
+        fs::remove_dir_all(&config.data_dir).unwrap();
+    }
+
+    #[test]
+    fn test_submit_respects_wait_time() {
+        let server = StubServer::start(vec![(200, TOO_RECENTLY)]);
+        let config = config(&server, "submit-wait");
+
+        assert_eq!(submit(&config, 2024, 7, 2, "42", 1000), Ok(Outcome::TooSoon(64)));
+        assert_eq!(submit(&config, 2024, 7, 2, "42", 1010), Err("wait 54s before submitting again".to_string()));
+
+        // nothing was judged, so the answer may be posted again later
+        let ledger = Ledger::load(&ledger_path(&config)).unwrap();
+        assert_eq!(ledger.attempts(2024, 7, 2).count(), 0);
+        assert_eq!(ledger.check(2024, 7, 2, "42", 1064), Check::Allowed);
+        fs::remove_dir_all(&config.data_dir).unwrap();
+    }
+
+    #[test]
+    fn test_unknown_page_is_not_recorded() {
+        let server = StubServer::start(vec![(200, "<p>Please log in to submit answers.</p>")]);
+        let config = config(&server, "submit-unknown");
+
+        assert_eq!(
+            submit(&config, 2024, 7, 1, "42", 0),
+            Ok(Outcome::Unknown("Please log in to submit answers.".to_string()))
+        );
+        let ledger = Ledger::load(&ledger_path(&config)).unwrap();
+        assert_eq!(ledger.attempts(2024, 7, 1).count(), 0);
+        let _ = fs::remove_dir_all(&config.data_dir);
+    }
+}
@@ -0,0 +1,204 @@
+use std::{iter::Chain, mem, ops::Index, slice, vec};
+
+/// Window over the last `capacity` pushed values, oldest first.
+pub struct CircularBuffer<T> {
+    data: Vec<T>,
+    capacity: usize,
+    // index of the oldest value once the buffer is full
+    start: usize,
+    hash: Option<RollingHash<T>>,
+}
+
+// window read as a number in base `base`, the oldest value being the most significant digit
+struct RollingHash<T> {
+    base: u64,
+    // base^(capacity - 1), weight of the oldest digit
+    top_power: u64,
+    digit: fn(&T) -> u64,
+    value: u64,
+}
+
+impl<T> CircularBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "circular buffer needs a positive capacity");
+
+        CircularBuffer {
+            data: Vec::with_capacity(capacity),
+            capacity,
+            start: 0,
+            hash: None,
+        }
+    }
+
+    /// Keeps a hash of the window updated on every push, see `rolling_hash`.
+    /// `digit` maps a value to a digit smaller than `base`.
+    pub fn with_rolling_hash(capacity: usize, base: u64, digit: fn(&T) -> u64) -> Self {
+        let mut buffer = Self::new(capacity);
+        buffer.hash = Some(RollingHash {
+            base,
+            top_power: base.wrapping_pow(capacity as u32 - 1),
+            digit,
+            value: 0,
+        });
+
+        buffer
+    }
+
+    /// Appends a value, returns the oldest one when it falls out of a full buffer.
+    pub fn push(&mut self, val: T) -> Option<T> {
+        let evicted = if self.data.len() < self.capacity {
+            self.data.push(val);
+            None
+        } else {
+            let old = mem::replace(&mut self.data[self.start], val);
+            self.start = (self.start + 1) % self.capacity;
+            Some(old)
+        };
+
+        if let Some(hash) = self.hash.as_mut() {
+            let newest = (hash.digit)(&self.data[(self.start + self.data.len() - 1) % self.capacity]);
+            let oldest = evicted.as_ref().map_or(0, |old| (hash.digit)(old));
+            hash.value = hash.value
+                .wrapping_sub(oldest.wrapping_mul(hash.top_power))
+                .wrapping_mul(hash.base)
+                .wrapping_add(newest);
+        }
+
+        evicted
+    }
+
+    /// The window contents as digits of a number, oldest first. Once the buffer is full
+    /// and digits are smaller than `base`, hashes are dense in `0..base^capacity`
+    /// and differ for different windows, as long as that range fits in `u64`.
+    /// `None` unless created by `with_rolling_hash`.
+    pub fn rolling_hash(&self) -> Option<u64> {
+        self.hash.as_ref().map(|hash| hash.value)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.data.len() == self.capacity
+    }
+
+    /// Value at `index`, counting from the oldest one.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.data.len() {
+            return None;
+        }
+
+        self.data.get((self.start + index) % self.capacity)
+    }
+
+    /// Oldest value.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Most recently pushed value.
+    pub fn back(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|last| self.get(last))
+    }
+
+    /// From the oldest to the newest value.
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (newer, older) = self.data.split_at(self.start);
+        older.iter().chain(newer.iter())
+    }
+}
+
+impl<T> Index<usize> for CircularBuffer<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index)
+            .unwrap_or_else(|| panic!("index {} out of a buffer of {}", index, self.len()))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CircularBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> IntoIterator for CircularBuffer<T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.data.rotate_left(self.start);
+        self.data.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_iter() {
+        let mut buffer = CircularBuffer::new(3);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.back(), None);
+
+        assert_eq!(buffer.push(1), None);
+        assert_eq!(buffer.push(2), None);
+        assert!(!buffer.is_full());
+        assert_eq!(buffer.iter().copied().collect::<Vec<i32>>(), [1, 2]);
+
+        assert_eq!(buffer.push(3), None);
+        assert_eq!(buffer.push(4), Some(1));
+        assert!(buffer.is_full());
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.iter().copied().collect::<Vec<i32>>(), [2, 3, 4]);
+        assert_eq!((buffer.front(), buffer.back()), (Some(&2), Some(&4)));
+        assert_eq!((buffer[0], buffer[2]), (2, 4));
+        assert_eq!(buffer.get(3), None);
+
+        let sum: i32 = (&buffer).into_iter().sum();
+        assert_eq!(sum, 9);
+        assert_eq!(buffer.into_iter().collect::<Vec<i32>>(), [2, 3, 4]);
+    }
+
+    #[test]
+    fn test_rolling_hash() {
+        let mut buffer = CircularBuffer::with_rolling_hash(3, 10, |d: &u8| *d as u64);
+        assert_eq!(CircularBuffer::<u8>::new(3).rolling_hash(), None);
+
+        for d in [1, 2, 3] {
+            buffer.push(d);
+        }
+        assert_eq!(buffer.rolling_hash(), Some(123));
+
+        buffer.push(4);
+        buffer.push(0);
+        assert_eq!(buffer.rolling_hash(), Some(340));
+    }
+
+    #[test]
+    fn test_rolling_hash_matches_contents() {
+        // digits of -9..=9 shifted to 0..19
+        let mut buffer = CircularBuffer::with_rolling_hash(4, 19, |c: &i8| (c + 9) as u64);
+
+        for (i, change) in [3, -9, 9, 0, -1, 5, 2, -7].into_iter().enumerate() {
+            buffer.push(change);
+            let expected = buffer.iter().fold(0, |acc, c| acc * 19 + (c + 9) as u64);
+            assert_eq!(buffer.rolling_hash(), Some(expected), "after {} pushes", i + 1);
+        }
+        assert!(buffer.rolling_hash().unwrap() < 19u64.pow(4));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{Signed, Vec2};
+
+/// One of the four grid directions, `y` grows downwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting from up.
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    /// Parses `^`, `>`, `v` or `<`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn as_char(&self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+
+    pub fn turn_right(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(&self) -> Self {
+        Self::ALL[(*self as usize + 3) % 4]
+    }
+
+    pub fn opposite(&self) -> Self {
+        Self::ALL[(*self as usize + 2) % 4]
+    }
+
+    /// Unit step in this direction.
+    pub fn offset<N: Signed>(&self) -> Vec2<N> {
+        let (zero, one) = (N::ZERO, N::ONE);
+        match self {
+            Direction::Up => (zero, -one),
+            Direction::Right => (one, zero),
+            Direction::Down => (zero, one),
+            Direction::Left => (-one, zero),
+        }.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Down.opposite(), Direction::Up);
+        assert!(Direction::ALL.iter().all(|d| d.turn_left().turn_right() == *d));
+    }
+
+    #[test]
+    fn test_chars() {
+        let parsed = "^>v<".chars().map(Direction::from_char).collect::<Option<Vec<Direction>>>();
+
+        assert_eq!(parsed, Some(Direction::ALL.to_vec()));
+        assert_eq!(Direction::from_char('x'), None);
+        assert_eq!(Direction::ALL.map(|d| d.as_char()).iter().collect::<String>(), "^>v<");
+    }
+
+    #[test]
+    fn test_offset_matches_rotation() {
+        for direction in Direction::ALL {
+            let offset: Vec2 = direction.offset();
+            assert_eq!(offset.rotate_right(), direction.turn_right().offset());
+            assert_eq!(-offset, direction.opposite().offset());
+        }
+        assert_eq!(Direction::Left.offset::<i64>(), (-1, 0).into());
+    }
+}
@@ -0,0 +1,199 @@
+use std::{fs, path::Path};
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{Answer, Solution};
+
+/// Contents of a day's `expected.toml`.
+///
+/// ```toml
+/// [[example]]
+/// input = """
+/// 1-3,5-7
+/// """
+/// part1 = 2
+/// params = { board_size = 7 }  # optional, see `check_examples`
+///
+/// [real]                       # answers for `input.txt`, optional
+/// part1 = 530
+/// part2 = "6,1"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    #[serde(default)]
+    pub example: Vec<Example>,
+    pub real: Option<Answers>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Example {
+    pub input: String,
+    pub part1: Option<ExpectedAnswer>,
+    pub part2: Option<ExpectedAnswer>,
+    pub params: Option<toml::Table>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    pub part1: Option<ExpectedAnswer>,
+    pub part2: Option<ExpectedAnswer>,
+}
+
+/// Answer as written in TOML, either a bare integer or a string.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ExpectedAnswer {
+    Number(i64),
+    Text(String),
+}
+
+impl From<&ExpectedAnswer> for Answer {
+    fn from(value: &ExpectedAnswer) -> Self {
+        match value {
+            ExpectedAnswer::Number(num) => Answer::from(*num),
+            ExpectedAnswer::Text(text) => Answer::from(text.as_str()),
+        }
+    }
+}
+
+impl Expected {
+    pub fn parse(expected_toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(expected_toml)
+    }
+}
+
+/// Asserts every `[[example]]` of `expected_toml` against `S`.
+///
+/// Examples without `params` run with `Params::default()`. Params structs
+/// are expected to use `#[serde(default)]`, so an example lists only the
+/// fields it changes.
+pub fn check_examples<S>(expected_toml: &str)
+    where S: Solution, S::Params: DeserializeOwned {
+
+    let expected = Expected::parse(expected_toml)
+        .unwrap_or_else(|e| panic!("invalid expected.toml: {}", e));
+    assert!(!expected.example.is_empty(), "expected.toml has no [[example]]");
+
+    for (idx, example) in expected.example.iter().enumerate() {
+        let params = match &example.params {
+            None => S::Params::default(),
+            Some(table) => S::Params::deserialize(toml::Value::Table(table.clone()))
+                .unwrap_or_else(|e| panic!("example {}: invalid params: {}", idx + 1, e)),
+        };
+        let answers = Answers { part1: example.part1.clone(), part2: example.part2.clone() };
+
+        check_answers::<S>(&format!("example {}", idx + 1), &example.input, &params, &answers);
+    }
+}
+
+/// Asserts the `[real]` answers of `expected_toml` against `S` run on `input_path`.
+/// Does nothing when either the answers or the input file are missing.
+pub fn check_real<S: Solution>(expected_toml: &str, input_path: impl AsRef<Path>) {
+    let expected = Expected::parse(expected_toml)
+        .unwrap_or_else(|e| panic!("invalid expected.toml: {}", e));
+
+    let (Some(answers), Ok(input)) = (expected.real, fs::read_to_string(input_path)) else {
+        return;
+    };
+
+    check_answers::<S>("real input", &input, &S::Params::default(), &answers);
+}
+
+fn check_answers<S: Solution>(name: &str, input: &str, params: &S::Params, answers: &Answers) {
+    for (part, expected) in [(1, &answers.part1), (2, &answers.part2)] {
+        let Some(expected) = expected else {
+            continue;
+        };
+
+        let answer = if part == 1 { S::part1(input, params) } else { S::part2(input, params) }
+            .unwrap_or_else(|e| panic!("{} part {}: {}", name, part, e));
+        assert_eq!(answer, Answer::from(expected), "{} part {}", name, part);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::parse_token, ParseResult};
+
+    struct Scaled;
+
+    #[derive(Deserialize)]
+    #[serde(default)]
+    struct ScaledParams {
+        factor: i64,
+        offset: i64,
+    }
+
+    impl Default for ScaledParams {
+        fn default() -> Self {
+            ScaledParams { factor: 10, offset: 0 }
+        }
+    }
+
+    impl Solution for Scaled {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 2;
+
+        type Params = ScaledParams;
+
+        fn part1(input: &str, params: &Self::Params) -> ParseResult<Answer> {
+            let num: i64 = parse_token(input, input.trim(), "number")?;
+            Ok(Answer::from(num * params.factor + params.offset))
+        }
+
+        fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+            Ok(Answer::from(format!("{},{}", input.trim(), input.trim())))
+        }
+    }
+
+    const EXPECTED: &str = r#"
+[[example]]
+input = "4"
+part1 = 40
+part2 = "4,4"
+
+[[example]]
+input = "4"
+part1 = 5
+params = { factor = 1, offset = 1 }
+
+[real]
+part1 = 70
+"#;
+
+    #[test]
+    fn test_parse_expected() {
+        let expected = Expected::parse(EXPECTED).unwrap();
+
+        assert_eq!(expected.example.len(), 2);
+        assert!(expected.example[1].part2.is_none());
+        assert_eq!(Answer::from(expected.example[0].part2.as_ref().unwrap()), Answer::Text("4,4".to_string()));
+        assert_eq!(Answer::from(expected.real.unwrap().part1.as_ref().unwrap()), Answer::Number(70));
+    }
+
+    #[test]
+    fn test_check_examples() {
+        check_examples::<Scaled>(EXPECTED);
+    }
+
+    #[test]
+    #[should_panic(expected = "example 1 part 1")]
+    fn test_check_examples_mismatch() {
+        check_examples::<Scaled>("[[example]]\ninput = \"4\"\npart1 = 41\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown field")]
+    fn test_unknown_field() {
+        check_examples::<Scaled>("[[example]]\ninput = \"4\"\npart3 = 41\n");
+    }
+
+    #[test]
+    fn test_check_real_skips_missing_input() {
+        check_real::<Scaled>(EXPECTED, "/nonexistent/input.txt");
+    }
+}
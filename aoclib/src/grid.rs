@@ -0,0 +1,825 @@
+use std::{cmp::Ordering, collections::{HashSet, VecDeque}};
+
+use crate::{search::{self, AllPathsResult}, GridView, ParseError, ParseResult, Region, Vec2};
+
+const INFINITY: i32 = i32::MAX;
+
+#[derive(Clone)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: i32,
+    height: i32,
+    topology: Topology,
+}
+
+/// Which cells are neighbours of a cell, used by `neighbors`, searches and flood fill.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Topology {
+    /// up, right, down and left
+    #[default]
+    Orthogonal,
+    /// orthogonal and diagonal, all 8 cells around
+    OrthogonalDiagonal,
+    /// orthogonal, leaving the grid through an edge enters it on the opposite one
+    Torus,
+    /// orthogonal and diagonal with wrapping edges
+    TorusDiagonal,
+}
+
+impl Topology {
+    const ORTHOGONAL: [Vec2; 4] = [
+        Vec2{x: 0, y: -1}, Vec2{x: 1, y: 0}, Vec2{x: 0, y: 1}, Vec2{x: -1, y: 0},
+    ];
+    const ORTHOGONAL_DIAGONAL: [Vec2; 8] = [
+        Vec2{x: 0, y: -1}, Vec2{x: 1, y: -1}, Vec2{x: 1, y: 0}, Vec2{x: 1, y: 1},
+        Vec2{x: 0, y: 1}, Vec2{x: -1, y: 1}, Vec2{x: -1, y: 0}, Vec2{x: -1, y: -1},
+    ];
+
+    /// Moves to the neighbours, clockwise starting from up.
+    pub fn offsets(&self) -> &'static [Vec2] {
+        match self {
+            Topology::Orthogonal | Topology::Torus => &Self::ORTHOGONAL,
+            Topology::OrthogonalDiagonal | Topology::TorusDiagonal => &Self::ORTHOGONAL_DIAGONAL,
+        }
+    }
+
+    pub fn wraps(&self) -> bool {
+        matches!(self, Topology::Torus | Topology::TorusDiagonal)
+    }
+}
+
+
+#[derive(Debug)]
+pub struct DijkstraResult {
+    pub cost: i32,
+    pub path: Vec<DijkstraState>,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct DijkstraState {
+    pub cost: i32,
+    pub idx: usize,
+}
+
+impl Ord for DijkstraState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Notice that we flip the ordering on costs.
+        // In case of a tie we compare positions - this step is necessary
+        // to make implementations of `PartialEq` and `Ord` consistent.
+        other.cost.cmp(&self.cost)
+            .then_with(|| self.idx.cmp(&other.idx))
+    }
+}
+
+impl PartialOrd for DijkstraState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
+impl<T> Grid<T> {
+    pub fn new_from_raw(data: Vec<T>, width: i32) -> Self {
+        Grid {
+            height: data.len() as i32 / width,
+            data,
+            width,
+            topology: Topology::default(),
+        }
+    }
+
+    pub fn new_empty_with_x_y_map<F>(width: i32, height: i32, map_func: F) -> Self 
+        where F: Fn(i32, i32) -> T {
+
+        let data = (0..height)
+            .flat_map(|y| {
+                (0..width)
+                    .map(|x| {
+                        map_func(x, y)
+                    })
+                    .collect::<Vec<T>>()
+            })
+            .collect();
+
+        Grid{
+            data,
+            width,
+            height,
+            topology: Topology::default(),
+        }
+    }
+
+    pub fn from_string_with_map<F>(input: &str, map_func: F) -> Self
+        where F: Fn(char) -> T {
+        
+        let height = input.lines().count();
+        let width = input.lines().next().unwrap_or("").len();
+
+        let data: Vec<T> = input
+            .lines()
+            .flat_map(|line| {
+                line
+                    .chars()
+                    .map(&map_func)
+                    .collect::<Vec<T>>()
+            })
+            .collect();
+
+        Grid { data, width: width as i32, height: height as i32, topology: Topology::default() }
+    }
+
+    /// Like `from_string_with_map`, but rejects unknown cells and ragged rows.
+    /// `map_func` returns `None` for characters that are not `expected`.
+    pub fn try_from_string_with_map<F>(input: &str, expected: &str, map_func: F) -> ParseResult<Self>
+        where F: Fn(char) -> Option<T> {
+
+        // widths are counted in cells, one per character
+        let width = input.lines().next().unwrap_or("").chars().count();
+        if width == 0 {
+            return Err(ParseError::at(input, input.lines().next().unwrap_or(input), expected));
+        }
+
+        let mut data: Vec<T> = Vec::with_capacity(input.len());
+        for line in input.lines() {
+            let mut cells = 0;
+            for (pos, c) in line.char_indices() {
+                let cell = map_func(c)
+                    .ok_or_else(|| ParseError::at(input, &line[pos..pos+c.len_utf8()], expected))?;
+                data.push(cell);
+                cells += 1;
+            }
+
+            if cells != width {
+                let extra = line.char_indices().nth(width).map_or(line.len(), |(pos, _)| pos);
+                return Err(ParseError::at(input, &line[extra..], format!("row of {} cells", width)));
+            }
+        }
+
+        Ok(Grid { height: (data.len() / width) as i32, data, width: width as i32, topology: Topology::default() })
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn get_at(&self, p: &Vec2) -> Option<&T> {
+        if !self.is_point_in_boundaries(p) {
+            None
+        } else { 
+            self.data.get(self.point2pos(p))
+        }
+    }
+
+    pub fn find<F>(&self, f: F) -> impl Iterator<Item = (usize, &T)>
+        where F: Fn(&T) -> bool
+    {
+        self
+            .data
+            .iter()
+            .enumerate()
+            .filter(move |&v| f(v.1))
+    }
+
+    pub fn find_one<F>(&self, f: F) -> Option<Vec2>
+        where F: Fn(&T) -> bool
+    {
+        self
+            .find(f)
+            .next()
+            .map(|(pos, _)| self.pos2point(pos))
+    }
+
+    pub fn swap_cells(&mut self, from: &Vec2, to: &Vec2) {
+        let from_pos = self.point2pos(from);
+        let to_pos = self.point2pos(to);
+
+        self.data.swap(from_pos, to_pos);
+    }
+
+    pub fn put_at(&mut self, point: Vec2, item: T) {
+        let pos = self.point2pos(&point);
+        *self.data.get_mut(pos).unwrap() = item;
+    }
+
+    pub fn visualization<F>(&self, map_func: F) -> String 
+        where F: Fn(&T) -> char
+    {
+        self
+            .data
+            .chunks(self.width as usize)
+            .map(|c| {
+                c.iter().map(&map_func).collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn is_point_in_boundaries(&self, point: &Vec2) -> bool {
+        point.x >= 0 && point.y >= 0
+            && point.x < self.width && point.y < self.height
+    }
+
+    pub fn map<'a, F, R>(&'a self, f: F) -> impl Iterator<Item = R> + 'a
+        where F: Fn((Vec2, &T)) -> R + 'a
+    {
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(pos, val)| {
+                let point = self.pos2point(pos);
+                f((point, val))
+            })
+    }
+
+    /// Borrowed view of the rectangle between two corners, both inclusive,
+    /// e.g. `Region::bounding_box`. The rectangle is clipped to the grid.
+    pub fn subgrid(&self, (top_left, bottom_right): (Vec2, Vec2)) -> GridView<'_, T> {
+        let top_left: Vec2 = (top_left.x.max(0), top_left.y.max(0)).into();
+        let bottom_right: Vec2 = (bottom_right.x.min(self.width - 1), bottom_right.y.min(self.height - 1)).into();
+        let size = bottom_right - top_left + (1, 1).into();
+
+        GridView::new(self, top_left, (size.x.max(0), size.y.max(0)).into())
+    }
+
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView::new(self, (0, 0).into(), self.size())
+    }
+
+    /// Rows from top to bottom, see `GridView::rows`.
+    pub fn rows(&self) -> impl Iterator<Item = Vec<(Vec2, &T)>> {
+        self.as_view().rows()
+    }
+
+    /// Columns from left to right, see `GridView::cols`.
+    pub fn cols(&self) -> impl Iterator<Item = Vec<(Vec2, &T)>> {
+        self.as_view().cols()
+    }
+
+    /// Lines going down and right, see `GridView::diagonals`.
+    pub fn diagonals(&self) -> impl Iterator<Item = Vec<(Vec2, &T)>> {
+        self.as_view().diagonals()
+    }
+
+    /// Lines going down and left, see `GridView::anti_diagonals`.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Vec<(Vec2, &T)>> {
+        self.as_view().anti_diagonals()
+    }
+
+    pub fn size(&self) -> Vec2 {
+        (self.width, self.height).into()
+    }
+
+    pub fn raw_size(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn dijkstra<F, G>(&self, start: Vec2, finish: Vec2, is_valid_point: F, calculate_distance: G) -> Option<DijkstraResult>
+        where F: Fn(&Vec2, &T) -> bool, // is_valid_point(point: &Vec2, vertex: &T)
+              G: Fn(&Vec2, &Vec2) -> i32, // calculate_distance(from: &Vec2, to: &Vec2)
+    {
+        self.astar(start, finish, is_valid_point, calculate_distance, |_| 0)
+    }
+
+    /// Every cheapest path from `start` to `finish`, see `search::dijkstra_all`.
+    pub fn dijkstra_all<F, G>(&self, start: Vec2, finish: Vec2, is_valid_point: F, calculate_distance: G) -> Option<AllPathsResult<Vec2>>
+        where F: Fn(&Vec2, &T) -> bool, // is_valid_point(point: &Vec2, vertex: &T)
+              G: Fn(&Vec2, &Vec2) -> i32, // calculate_distance(from: &Vec2, to: &Vec2)
+    {
+        search::dijkstra_all(
+            start,
+            |&point| {
+                self.valid_neighbors(point, &is_valid_point)
+                    .map(|neighbor_point| (neighbor_point, calculate_distance(&point, &neighbor_point)))
+                    .collect::<Vec<(Vec2, i32)>>()
+            },
+            |point| *point == finish,
+        )
+    }
+
+    /// Dijkstra guided by `heuristic`, the estimated cost from a point to `finish`.
+    /// The heuristic must never overestimate, otherwise the result is not the cheapest path.
+    pub fn astar<F, G, H>(&self, start: Vec2, finish: Vec2, is_valid_point: F, calculate_distance: G, heuristic: H) -> Option<DijkstraResult>
+        where F: Fn(&Vec2, &T) -> bool, // is_valid_point(point: &Vec2, vertex: &T)
+              G: Fn(&Vec2, &Vec2) -> i32, // calculate_distance(from: &Vec2, to: &Vec2)
+              H: Fn(&Vec2) -> i32, // heuristic(point: &Vec2)
+    {
+        let finish_pos = self.point2pos(&finish);
+
+        let result = search::astar(
+            self.point2pos(&start),
+            |&idx| {
+                let point = self.pos2point(idx);
+                self.valid_neighbors(point, &is_valid_point)
+                    .map(|neighbor_point| (self.point2pos(&neighbor_point), calculate_distance(&point, &neighbor_point)))
+                    .collect::<Vec<(usize, i32)>>()
+            },
+            |&idx| idx == finish_pos,
+            |&idx| heuristic(&self.pos2point(idx)),
+        )?;
+
+        // grid paths carry the cost of reaching each cell and stop before `finish`
+        let mut cost = 0;
+        let path = result.path
+            .windows(2)
+            .map(|step| {
+                let state = DijkstraState{cost, idx: step[0]};
+                cost += calculate_distance(&self.pos2point(step[0]), &self.pos2point(step[1]));
+                state
+            })
+            .collect();
+
+        Some(DijkstraResult{cost: result.cost, path})
+    }
+
+    /// Shortest path when every move costs 1.
+    pub fn bfs<F>(&self, start: Vec2, finish: Vec2, is_valid_point: F) -> Option<DijkstraResult>
+        where F: Fn(&Vec2, &T) -> bool, // is_valid_point(point: &Vec2, vertex: &T)
+    {
+        let finish_pos = self.point2pos(&finish);
+        let (dist, previous) = self.bfs_until(start, Some(finish_pos), is_valid_point);
+
+        if dist[finish_pos] == INFINITY {
+            return None;
+        }
+
+        Some(DijkstraResult{
+            cost: dist[finish_pos],
+            path: Self::unwind_path(&previous, &dist, finish_pos),
+        })
+    }
+
+    /// Number of moves from `start` to every point, `None` for unreachable points.
+    /// Unlike `bfs` it does not stop early, so a single call answers all distance queries.
+    pub fn distances_from<F>(&self, start: Vec2, is_valid_point: F) -> Grid<Option<i32>>
+        where F: Fn(&Vec2, &T) -> bool, // is_valid_point(point: &Vec2, vertex: &T)
+    {
+        let (dist, _) = self.bfs_until(start, None, is_valid_point);
+
+        Grid::new_from_raw(
+            dist.into_iter()
+                .map(|d| if d == INFINITY { None } else { Some(d) })
+                .collect(),
+            self.width,
+        ).with_topology(self.topology)
+    }
+
+    /// Points connected to `start` through valid points, `start` included.
+    pub fn flood_fill<F>(&self, start: Vec2, is_valid_point: F) -> Vec<Vec2>
+        where F: Fn(&Vec2, &T) -> bool, // is_valid_point(point: &Vec2, vertex: &T)
+    {
+        let (dist, _) = self.bfs_until(start, None, is_valid_point);
+
+        dist.iter()
+            .enumerate()
+            .filter(|(_, &d)| d != INFINITY)
+            .map(|(pos, _)| self.pos2point(pos))
+            .collect()
+    }
+
+    /// Splits the grid into connected components of cells for which `eq` holds
+    /// between neighbours. Labels are the indexes in the returned vector,
+    /// regions are ordered by their first cell in reading order.
+    pub fn regions<F>(&self, eq: F) -> Vec<Region>
+        where F: Fn(&T, &T) -> bool, // eq(cell: &T, neighbor: &T)
+    {
+        let mut labels: Vec<Option<usize>> = vec![None; self.data.len()];
+        let mut regions = vec![];
+
+        for start_pos in 0..self.data.len() {
+            if labels[start_pos].is_some() {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut cells = HashSet::new();
+            let mut stack = vec![start_pos];
+            labels[start_pos] = Some(label);
+
+            while let Some(idx) = stack.pop() {
+                let point = self.pos2point(idx);
+                cells.insert(point);
+
+                for neighbor_point in self.neighbors(point) {
+                    let neighbor_pos = self.point2pos(&neighbor_point);
+                    if labels[neighbor_pos].is_none() && eq(&self.data[idx], &self.data[neighbor_pos]) {
+                        labels[neighbor_pos] = Some(label);
+                        stack.push(neighbor_pos);
+                    }
+                }
+            }
+
+            regions.push(Region::new(label, cells));
+        }
+
+        regions
+    }
+
+    /// Neighbours of `p` inside the grid according to the grid topology, each one once
+    /// and never `p` itself.
+    pub fn neighbors(&self, p: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        // on a torus 1 or 2 cells wide or tall, offsets wrap onto `p` or onto each other
+        let mut seen = [p; 9];
+        let mut seen_count = 1;
+
+        self.topology
+            .offsets()
+            .iter()
+            .filter_map(move |&offset| {
+                let neighbor = p + offset;
+                if !self.topology.wraps() {
+                    return self.is_point_in_boundaries(&neighbor).then_some(neighbor);
+                }
+
+                let neighbor = self.wrap_point(neighbor);
+                if seen[..seen_count].contains(&neighbor) {
+                    return None;
+                }
+                seen[seen_count] = neighbor;
+                seen_count += 1;
+
+                Some(neighbor)
+            })
+    }
+
+    /// Moves a point lying outside the grid back in, as if the edges were glued together.
+    pub fn wrap_point(&self, p: Vec2) -> Vec2 {
+        (p.x.rem_euclid(self.width), p.y.rem_euclid(self.height)).into()
+    }
+
+    fn bfs_until<F>(&self, start: Vec2, finish_pos: Option<usize>, is_valid_point: F) -> (Vec<i32>, Vec<Option<usize>>)
+        where F: Fn(&Vec2, &T) -> bool,
+    {
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        let mut dist: Vec<i32> = vec![INFINITY; self.data.len()];
+        let mut previous: Vec<Option<usize>> = vec![None; self.data.len()];
+
+        let start_pos = self.point2pos(&start);
+        dist[start_pos] = 0;
+        queue.push_back(start_pos);
+
+        while let Some(idx) = queue.pop_front() {
+            if Some(idx) == finish_pos {
+                break;
+            }
+
+            let current_point = self.pos2point(idx);
+            for neighbor_point in self.valid_neighbors(current_point, &is_valid_point) {
+                let neighbor_pos = self.point2pos(&neighbor_point);
+
+                // the first visit is the closest one
+                if dist[neighbor_pos] == INFINITY {
+                    dist[neighbor_pos] = dist[idx] + 1;
+                    previous[neighbor_pos] = Some(idx);
+                    queue.push_back(neighbor_pos);
+                }
+            }
+        }
+
+        (dist, previous)
+    }
+
+    fn valid_neighbors<'a, F>(&'a self, point: Vec2, is_valid_point: &'a F) -> impl Iterator<Item = Vec2> + 'a
+        where F: Fn(&Vec2, &T) -> bool,
+    {
+        self.neighbors(point)
+            .filter(move |neighbor_point| is_valid_point(neighbor_point, &self.data[self.point2pos(neighbor_point)]))
+    }
+
+    // path from start up to, but excluding, `next`
+    fn unwind_path(previous: &[Option<usize>], dist: &[i32], next: usize) -> Vec<DijkstraState> {
+        let mut next_point = previous[next];
+        let mut res: Vec<DijkstraState> = vec![];
+        while let Some(next_pos) = next_point {
+            res.push(DijkstraState{
+                cost: dist[next_pos],
+                idx: next_pos,
+            });
+            next_point = previous[next_pos];
+        } 
+        res.reverse();
+
+        res
+    }
+
+    pub fn point2pos(&self, p: &Vec2) -> usize {
+        (p.x + p.y * self.width) as usize
+    }
+    
+    pub fn pos2point(&self, pos: usize) -> Vec2 {
+        let y = pos as i32 / self.width;
+        let x = pos as i32 % self.width;
+
+        (x, y).into()
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Swaps rows with columns.
+    pub fn transpose(&self) -> Self {
+        self.transformed(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotates clockwise by 90 degrees.
+    pub fn rotate_cw(&self) -> Self {
+        self.transformed(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_h(&self) -> Self {
+        self.transformed(self.width, self.height, |x, y| (self.width - 1 - x, y))
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_v(&self) -> Self {
+        self.transformed(self.width, self.height, |x, y| (x, self.height - 1 - y))
+    }
+
+    // `source` maps a point of the new grid to the point of `self` it is copied from
+    fn transformed<F>(&self, width: i32, height: i32, source: F) -> Self
+        where F: Fn(i32, i32) -> (i32, i32)
+    {
+        Grid::new_empty_with_x_y_map(width, height, |x, y| {
+            self.data[self.point2pos(&source(x, y).into())].clone()
+        }).with_topology(self.topology)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "...#
+.#.#
+.#..
+...#";
+
+    fn maze() -> Grid<char> {
+        Grid::from_string_with_map(MAZE, |c| c)
+    }
+
+    #[test]
+    fn test_from_string_with_map() {
+        let grid = maze();
+
+        assert_eq!(grid.size(), Vec2{x: 4, y: 4});
+        assert_eq!(grid.raw_size(), 16);
+        assert_eq!(grid.visualization(|c| *c), MAZE);
+    }
+
+    #[test]
+    fn test_try_from_string_with_map() {
+        let wall = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+
+        let grid = Grid::try_from_string_with_map(MAZE, "`#` or `.`", wall).unwrap();
+        assert_eq!(grid.size(), Vec2{x: 4, y: 4});
+
+        let err = Grid::try_from_string_with_map("..\n.x", "`#` or `.`", wall).err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 2: expected `#` or `.`, found `x`");
+
+        let err = Grid::try_from_string_with_map("..\n...", "`#` or `.`", wall).err().unwrap();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (2, 3, "row of 2 cells"));
+
+        // cells are characters, not bytes
+        let arrow = |c| match c {
+            '→' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let grid = Grid::try_from_string_with_map("→.
+.→", "`→` or `.`", arrow).unwrap();
+        assert_eq!(grid.size(), Vec2{x: 2, y: 2});
+
+        let err = Grid::try_from_string_with_map("→.
+.→→", "`→` or `.`", arrow).err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 3: expected row of 2 cells, found `→`");
+    }
+
+    #[test]
+    fn test_get_at_out_of_boundaries() {
+        let grid = maze();
+
+        assert_eq!(grid.get_at(&(3, 0).into()), Some(&'#'));
+        assert_eq!(grid.get_at(&(4, 0).into()), None);
+        assert_eq!(grid.get_at(&(0, 4).into()), None);
+        assert_eq!(grid.get_at(&(-1, 2).into()), None);
+    }
+
+    #[test]
+    fn test_put_and_swap() {
+        let mut grid = maze();
+
+        grid.put_at((0, 0).into(), '@');
+        grid.swap_cells(&(0, 0).into(), &(1, 0).into());
+
+        assert_eq!(grid.find_one(|c| *c == '@'), Some((1, 0).into()));
+        assert_eq!(grid.get_at(&(0, 0).into()), Some(&'.'));
+    }
+
+    #[test]
+    fn test_pos_point_conversion() {
+        let grid = maze();
+
+        assert_eq!(grid.point2pos(&(2, 1).into()), 6);
+        assert_eq!(grid.pos2point(6), (2, 1).into());
+    }
+
+    #[test]
+    fn test_new_empty_with_x_y_map() {
+        let grid = Grid::new_empty_with_x_y_map(3, 2, |x, y| x + y * 10);
+
+        assert_eq!(grid.size(), Vec2{x: 3, y: 2});
+        assert_eq!(grid.get_at(&(2, 1).into()), Some(&12));
+        assert_eq!(grid.map(|(_, v)| *v).sum::<i32>(), 3 + 33);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let grid = maze();
+
+        let result = grid.dijkstra(
+            (0, 0).into(),
+            (3, 2).into(),
+            |_, c| *c != '#',
+            |_, _| 1,
+        ).unwrap();
+
+        assert_eq!(result.cost, 5);
+        assert_eq!(result.path.len(), 5);
+        assert_eq!(result.path.first().map(|s| s.idx), Some(0));
+        assert_eq!(result.path.last().map(|s| grid.pos2point(s.idx)), Some((2, 2).into()));
+    }
+
+    #[test]
+    fn test_dijkstra_unreachable() {
+        let grid = maze();
+
+        let result = grid.dijkstra(
+            (0, 0).into(),
+            (3, 2).into(),
+            |p, c| *c != '#' && *p != Vec2{x: 2, y: 2},
+            |_, _| 1,
+        );
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_dijkstra_all() {
+        let grid = maze();
+
+        // both ways around the wall in the middle column are equally long
+        let result = grid.dijkstra_all((0, 0).into(), (2, 3).into(), |_, c| *c != '#', |_, _| 1).unwrap();
+        assert_eq!(result.cost, 5);
+        assert_eq!(result.count(), 2);
+        assert_eq!(result.paths().count(), 2);
+        // every open cell but the dead end on the right
+        assert_eq!(result.on_any_path().len(), 10);
+        assert!(!result.on_any_path().contains(&(3, 2).into()));
+
+        let result = grid.dijkstra_all((0, 2).into(), (2, 2).into(), |_, c| *c != '#', |_, _| 1).unwrap();
+        assert_eq!(result.cost, 4);
+        assert_eq!(result.count(), 1);
+        assert_eq!(result.paths().next().unwrap().len(), 5);
+        assert!(result.on_any_path().contains(&(1, 3).into()));
+    }
+
+    #[test]
+    fn test_neighbors_topology() {
+        let grid = maze();
+        let neighbors = |grid: &Grid<char>, p: (i32, i32)| grid.neighbors(p.into()).collect::<Vec<Vec2>>();
+
+        assert_eq!(neighbors(&grid, (0, 0)), vec![(1, 0).into(), (0, 1).into()]);
+
+        let grid = grid.with_topology(Topology::OrthogonalDiagonal);
+        assert_eq!(neighbors(&grid, (0, 0)), vec![(1, 0).into(), (1, 1).into(), (0, 1).into()]);
+        assert_eq!(neighbors(&grid, (1, 1)).len(), 8);
+
+        let grid = grid.with_topology(Topology::Torus);
+        assert_eq!(neighbors(&grid, (0, 0)), vec![(0, 3).into(), (1, 0).into(), (0, 1).into(), (3, 0).into()]);
+        assert_eq!(grid.wrap_point((-1, 9).into()), (3, 1).into());
+
+        let grid = grid.with_topology(Topology::TorusDiagonal);
+        assert!(neighbors(&grid, (0, 0)).contains(&(3, 3).into()));
+
+        // up and down, left and right wrap onto the same cells
+        let small = Grid::from_string_with_map("ab\ncd", |c| c).with_topology(Topology::Torus);
+        assert_eq!(neighbors(&small, (0, 0)), vec![(0, 1).into(), (1, 0).into()]);
+        let small = small.with_topology(Topology::TorusDiagonal);
+        assert_eq!(neighbors(&small, (1, 1)), vec![(1, 0).into(), (0, 0).into(), (0, 1).into()]);
+
+        let single = Grid::from_string_with_map("a", |c| c).with_topology(Topology::TorusDiagonal);
+        assert_eq!(neighbors(&single, (0, 0)), vec![]);
+    }
+
+    #[test]
+    fn test_search_honours_topology() {
+        let grid = maze();
+        let open = |_: &Vec2, c: &char| *c != '#';
+
+        assert_eq!(grid.bfs((0, 0).into(), (3, 2).into(), open).unwrap().cost, 5);
+        // through the corner of the walls on the right
+        let diagonal = grid.with_topology(Topology::OrthogonalDiagonal);
+        assert_eq!(diagonal.bfs((0, 0).into(), (3, 2).into(), open).unwrap().cost, 3);
+        // a step left from the left edge enters at the right one
+        assert_eq!(maze().dijkstra((0, 2).into(), (3, 2).into(), open, |_, _| 1).unwrap().cost, 5);
+        let torus = maze().with_topology(Topology::Torus);
+        assert_eq!(torus.dijkstra((0, 2).into(), (3, 2).into(), open, |_, _| 1).unwrap().cost, 1);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = Grid::from_string_with_map("..#.\n..#.\n##..", |c| c);
+
+        let mut filled = grid.flood_fill((0, 0).into(), |_, c| *c == '.');
+        filled.sort();
+        assert_eq!(filled, vec![(0, 0).into(), (0, 1).into(), (1, 0).into(), (1, 1).into()]);
+
+        let diagonal = grid.with_topology(Topology::OrthogonalDiagonal);
+        assert_eq!(diagonal.flood_fill((0, 0).into(), |_, c| *c == '.').len(), 8);
+    }
+
+    #[test]
+    fn test_regions() {
+        let grid = Grid::from_string_with_map("AAB\nABB\nCCB", |c| c);
+
+        let regions = grid.regions(|a, b| a == b);
+        assert_eq!(regions.len(), 3);
+        assert_eq!(regions.iter().map(|r| r.label).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(regions.iter().map(|r| r.area()).collect::<Vec<_>>(), vec![3, 4, 2]);
+        assert!(regions[1].contains(&(2, 2).into()));
+
+        // cells of the same kind touch only diagonally
+        let grid = Grid::from_string_with_map("AB\nBA", |c| c);
+        assert_eq!(grid.regions(|a, b| a == b).len(), 4);
+        assert_eq!(grid.with_topology(Topology::OrthogonalDiagonal).regions(|a, b| a == b).len(), 2);
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = Grid::from_string_with_map("abc\ndef", |c| c);
+
+        assert_eq!(grid.transpose().visualization(|c| *c), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_cw().visualization(|c| *c), "da\neb\nfc");
+        assert_eq!(grid.flip_h().visualization(|c| *c), "cba\nfed");
+        assert_eq!(grid.flip_v().visualization(|c| *c), "def\nabc");
+        assert_eq!(grid.rotate_cw().rotate_cw().visualization(|c| *c), "fed\ncba");
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = Grid::from_string_with_map("ab\ncd", |c| c);
+        let lines = |lines: Vec<Vec<(Vec2, &char)>>| lines.iter()
+            .map(|line| line.iter().map(|(_, c)| **c).collect::<String>())
+            .collect::<Vec<String>>();
+
+        assert_eq!(lines(grid.rows().collect()), vec!["ab", "cd"]);
+        assert_eq!(lines(grid.cols().collect()), vec!["ac", "bd"]);
+        assert_eq!(lines(grid.diagonals().collect()), vec!["c", "ad", "b"]);
+        assert_eq!(lines(grid.anti_diagonals().collect()), vec!["a", "bc", "d"]);
+        assert_eq!(grid.cols().nth(1).unwrap()[1], ((1, 1).into(), &'d'));
+    }
+
+    #[test]
+    fn test_astar_matches_dijkstra() {
+        let grid = maze();
+        let finish: Vec2 = (3, 2).into();
+        let manhattan = |p: &Vec2| (finish.x - p.x).abs() + (finish.y - p.y).abs();
+
+        let result = grid.astar((0, 0).into(), finish, |_, c| *c != '#', |_, _| 1, manhattan).unwrap();
+        let reference = grid.dijkstra((0, 0).into(), finish, |_, c| *c != '#', |_, _| 1).unwrap();
+
+        assert_eq!(result.cost, reference.cost);
+        assert_eq!(result.path.len(), reference.path.len());
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+
+        let result = grid.bfs((0, 0).into(), (3, 2).into(), |_, c| *c != '#').unwrap();
+        assert_eq!(result.cost, 5);
+        assert_eq!(result.path.iter().map(|s| s.cost).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+
+        assert!(grid.bfs((0, 0).into(), (3, 0).into(), |_, c| *c != '#').is_none());
+    }
+
+    #[test]
+    fn test_distances_from() {
+        let grid = maze();
+
+        let distances = grid.distances_from((0, 0).into(), |_, c| *c != '#');
+        assert_eq!(
+            distances.visualization(|d| d.map_or('#', |d| char::from_digit(d as u32, 10).unwrap())),
+            "012#\n1#3#\n2#45\n345#"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{Grid, Vec2};
+
+/// Borrowed rectangular part of a `Grid`, see `Grid::subgrid`.
+/// Coordinates are relative to the top left corner of the view.
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    top_left: Vec2,
+    size: Vec2,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub(crate) fn new(grid: &'a Grid<T>, top_left: Vec2, size: Vec2) -> Self {
+        GridView { grid, top_left, size }
+    }
+
+    pub fn get_at(&self, p: &Vec2) -> Option<&'a T> {
+        if !self.is_point_in_boundaries(p) {
+            None
+        } else {
+            self.grid.get_at(&(self.top_left + *p))
+        }
+    }
+
+    pub fn is_point_in_boundaries(&self, point: &Vec2) -> bool {
+        point.x >= 0 && point.y >= 0
+            && point.x < self.size.x && point.y < self.size.y
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// Position of the view in the grid it borrows from.
+    pub fn top_left(&self) -> Vec2 {
+        self.top_left
+    }
+
+    pub fn map<F, R>(self, f: F) -> impl Iterator<Item = R> + 'a
+        where F: Fn((Vec2, &'a T)) -> R + 'a
+    {
+        self.rows().flatten().map(f)
+    }
+
+    pub fn visualization<F>(&self, map_func: F) -> String
+        where F: Fn(&T) -> char
+    {
+        self.rows()
+            .map(|row| row.into_iter().map(|(_, item)| map_func(item)).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Rows from top to bottom, each from left to right.
+    pub fn rows(self) -> impl Iterator<Item = Vec<(Vec2, &'a T)>> + 'a {
+        (0..self.size.y).map(move |y| self.line((0, y).into(), (1, 0).into()))
+    }
+
+    /// Columns from left to right, each from top to bottom.
+    pub fn cols(self) -> impl Iterator<Item = Vec<(Vec2, &'a T)>> + 'a {
+        (0..self.size.x).map(move |x| self.line((x, 0).into(), (0, 1).into()))
+    }
+
+    /// Lines going down and right, starting from the bottom left corner
+    /// and ending with the top right one.
+    pub fn diagonals(self) -> impl Iterator<Item = Vec<(Vec2, &'a T)>> + 'a {
+        (0..self.size.y).rev().map(|y| (0, y))
+            .chain((1..self.size.x).map(|x| (x, 0)))
+            .map(move |start| self.line(start.into(), (1, 1).into()))
+    }
+
+    /// Lines going down and left, starting from the top left corner
+    /// and ending with the bottom right one.
+    pub fn anti_diagonals(self) -> impl Iterator<Item = Vec<(Vec2, &'a T)>> + 'a {
+        (0..self.size.x).map(|x| (x, 0))
+            .chain((1..self.size.y).map(move |y| (self.size.x - 1, y)))
+            .map(move |start| self.line(start.into(), (-1, 1).into()))
+    }
+
+    fn line(&self, start: Vec2, step: Vec2) -> Vec<(Vec2, &'a T)> {
+        let mut line = vec![];
+        let mut point = start;
+        while let Some(item) = self.get_at(&point) {
+            line.push((point, item));
+            point += step;
+        }
+
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::from_string_with_map("abc\ndef\nghi\njkl", |c| c)
+    }
+
+    fn words<'a>(lines: impl Iterator<Item = Vec<(Vec2, &'a char)>>) -> Vec<String> {
+        lines.map(|line| line.iter().map(|(_, c)| **c).collect()).collect()
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = letters();
+        let view = grid.subgrid(((1, 1).into(), (2, 2).into()));
+
+        assert_eq!(view.size(), (2, 2).into());
+        assert_eq!(view.get_at(&(0, 0).into()), Some(&'e'));
+        assert_eq!(view.get_at(&(2, 0).into()), None);
+        assert_eq!(view.visualization(|c| *c), "ef\nhi");
+        assert_eq!(view.map(|(p, _)| p).last(), Some((1, 1).into()));
+    }
+
+    #[test]
+    fn test_view_is_clipped_to_grid() {
+        let grid = letters();
+        let view = grid.subgrid(((-1, 2).into(), (1, 9).into()));
+
+        assert_eq!(view.top_left(), (0, 2).into());
+        assert_eq!(view.visualization(|c| *c), "gh\njk");
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = letters();
+        let view = grid.subgrid(((0, 0).into(), (2, 3).into()));
+
+        assert_eq!(words(view.rows()), vec!["abc", "def", "ghi", "jkl"]);
+        assert_eq!(words(view.cols()), vec!["adgj", "behk", "cfil"]);
+        assert_eq!(words(view.diagonals()), vec!["j", "gk", "dhl", "aei", "bf", "c"]);
+        assert_eq!(words(view.anti_diagonals()), vec!["a", "bd", "ceg", "fhj", "ik", "l"]);
+        assert_eq!(view.diagonals().nth(2).unwrap()[1], ((1, 2).into(), &'h'));
+    }
+}
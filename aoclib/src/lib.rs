@@ -0,0 +1,86 @@
+mod circular_buffer;
+mod direction;
+pub mod expected;
+mod grid;
+mod grid_view;
+pub mod linear;
+pub mod math;
+mod memo;
+mod num;
+pub mod parse;
+mod range_set;
+#[cfg(feature = "render")]
+pub mod render;
+mod region;
+pub mod search;
+pub mod simulation;
+mod solution;
+mod sparse_grid;
+pub mod terminal;
+mod vec2;
+mod vec3;
+#[cfg(feature = "viewer")]
+pub mod viewer;
+
+pub use circular_buffer::CircularBuffer;
+pub use direction::Direction;
+pub use grid::{DijkstraResult, DijkstraState, Grid, Topology};
+pub use grid_view::GridView;
+pub use memo::{Memo, Memoized};
+pub use num::{Integer, Signed};
+pub use parse::{ParseError, ParseResult};
+pub use range_set::RangeSet;
+pub use region::Region;
+pub use simulation::Simulation;
+pub use solution::{Answer, Puzzle, Solution};
+pub use sparse_grid::SparseGrid;
+pub use vec2::Vec2;
+pub use vec3::Vec3;
+
+/// Generates the `expected` test module asserting a day against its `expected.toml`.
+///
+/// Real-input answers are checked by an ignored test, run it with
+/// `cargo test --release -- --ignored`.
+#[macro_export]
+macro_rules! expected_tests {
+    ($solution:ty) => {
+        #[cfg(test)]
+        mod expected {
+            use super::*;
+
+            const EXPECTED: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/expected.toml"));
+
+            #[test]
+            fn examples() {
+                $crate::expected::check_examples::<$solution>(EXPECTED);
+            }
+
+            #[test]
+            #[ignore = "solves the real puzzle input"]
+            fn real_input() {
+                $crate::expected::check_real::<$solution>(EXPECTED, concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"));
+            }
+        }
+    };
+}
+
+pub fn str_to_int(input: &str) -> i32 {
+    input
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str_to_int() {
+        assert_eq!(str_to_int("029A"), 29);
+        assert_eq!(str_to_int("980A"), 980);
+        assert_eq!(str_to_int("no digits"), 0);
+    }
+}
@@ -0,0 +1,326 @@
+use std::cmp::Ordering;
+
+use crate::math::{self, Rational};
+
+type Fraction = Rational<i128>;
+
+/// Linear equations over non-negative integer variables, each with a cost,
+/// searched for the cheapest solution.
+///
+/// ```
+/// use aoclib::linear::IntegerSystem;
+///
+/// // button A costs 3, button B costs 1
+/// let presses = IntegerSystem::new(&[3, 1])
+///     .equation(&[94, 22], 8400)
+///     .equation(&[34, 67], 5400)
+///     .cheapest();
+/// assert_eq!(presses.map(|p| p.values), Some(vec![80, 40]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct IntegerSystem {
+    equations: Vec<(Vec<i128>, i128)>,
+    costs: Vec<i128>,
+    max_value: Option<i128>,
+}
+
+/// Values of the variables and their total cost.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntegerSolution {
+    pub values: Vec<i128>,
+    pub cost: i128,
+}
+
+// reduced row echelon form, `rows[i]` expresses the variable `pivots[i]`
+struct Echelon {
+    rows: Vec<(Vec<Fraction>, Fraction)>,
+    pivots: Vec<usize>,
+    free: Vec<usize>,
+}
+
+impl IntegerSystem {
+    /// System over `costs.len()` variables, without equations yet.
+    pub fn new(costs: &[i128]) -> Self {
+        IntegerSystem {
+            equations: vec![],
+            costs: costs.to_vec(),
+            max_value: None,
+        }
+    }
+
+    /// Adds the equation `coefficients · x = value`.
+    pub fn equation(mut self, coefficients: &[i128], value: i128) -> Self {
+        assert_eq!(coefficients.len(), self.costs.len(), "one coefficient per variable");
+
+        self.equations.push((coefficients.to_vec(), value));
+        self
+    }
+
+    /// Limits every variable to at most `max_value`.
+    pub fn with_max_value(mut self, max_value: i128) -> Self {
+        self.max_value = Some(max_value);
+        self
+    }
+
+    /// Cheapest solution, the first one found on ties. A single degree of freedom,
+    /// like collinear claw machine buttons, is solved exactly whatever the values.
+    /// Any further free variable is searched over `0..=max_value`, or up to the bound
+    /// given by an equation with non-negative coefficients.
+    ///
+    /// `None` when there is no solution, but also when there is no cheapest one: a
+    /// free variable lowering the cost without an upper bound, or a second free
+    /// variable that neither `max_value` nor an equation bounds.
+    pub fn cheapest(&self) -> Option<IntegerSolution> {
+        let echelon = self.reduce()?;
+
+        match echelon.free[..] {
+            [] => self.solution(&echelon, None, 0),
+            [free] => self.cheapest_along(&echelon, free),
+            [free, ..] => {
+                // nothing to search over
+                let bound = self.bound(free)?;
+
+                let mut fixed = vec![0; self.costs.len()];
+                fixed[free] = 1;
+
+                (0..=bound)
+                    .filter_map(|value| self.clone().equation(&fixed, value).cheapest())
+                    .min_by_key(|solution| solution.cost)
+            }
+        }
+    }
+
+    // Gauss-Jordan elimination, `None` when the equations contradict each other
+    fn reduce(&self) -> Option<Echelon> {
+        let variables = self.costs.len();
+        let mut rows: Vec<(Vec<Fraction>, Fraction)> = self.equations
+            .iter()
+            .map(|(coefficients, value)| {
+                (coefficients.iter().map(|&c| Fraction::from(c)).collect(), Fraction::from(*value))
+            })
+            .collect();
+
+        let zero = Fraction::from(0);
+        let mut pivots = vec![];
+        for col in 0..variables {
+            let Some(found) = (pivots.len()..rows.len()).find(|&r| rows[r].0[col] != zero) else {
+                continue;
+            };
+
+            let row = pivots.len();
+            rows.swap(row, found);
+            let pivot = rows[row].0[col];
+            rows[row].0.iter_mut().for_each(|c| *c = *c / pivot);
+            rows[row].1 = rows[row].1 / pivot;
+
+            for other in 0..rows.len() {
+                let factor = rows[other].0[col];
+                if other == row || factor == zero {
+                    continue;
+                }
+
+                for c in 0..variables {
+                    let reduced = rows[other].0[c] - factor * rows[row].0[c];
+                    rows[other].0[c] = reduced;
+                }
+                rows[other].1 = rows[other].1 - factor * rows[row].1;
+            }
+
+            pivots.push(col);
+        }
+
+        // rows left over read 0 = value
+        if rows[pivots.len()..].iter().any(|(_, value)| *value != zero) {
+            return None;
+        }
+        rows.truncate(pivots.len());
+
+        let free = (0..variables).filter(|v| !pivots.contains(v)).collect();
+        Some(Echelon { rows, pivots, free })
+    }
+
+    // Every pivot variable is `value - coefficient * t` for the free variable `t`,
+    // so integer values need congruences on `t`, non-negative ones give its range
+    // and the cost is linear in `t`.
+    fn cheapest_along(&self, echelon: &Echelon, free: usize) -> Option<IntegerSolution> {
+        let mut congruences = vec![];
+        let (mut low, mut high) = (0, self.max_value);
+
+        for (coefficients, value) in echelon.rows.iter() {
+            let (value, coefficient) = (*value, coefficients[free]);
+
+            let denom = math::lcm(value.denom(), coefficient.denom());
+            let (r, s) = ((value * denom.into()).numer(), (coefficient * denom.into()).numer());
+            // s * t ≡ r (mod denom)
+            let g = math::gcd(s, denom);
+            if r % g != 0 {
+                return None;
+            }
+            if denom / g > 1 {
+                let inverse = math::mod_inverse(s / g, denom / g)?;
+                congruences.push((r / g * inverse % (denom / g), denom / g));
+            }
+
+            let zero = Fraction::from(0);
+            // value - coefficient * t >= 0
+            match coefficient.cmp(&zero) {
+                Ordering::Greater => high = Some(min_bound(high, (value / coefficient).floor())),
+                Ordering::Less => low = low.max((value / coefficient).ceil()),
+                Ordering::Equal if value < zero => return None,
+                _ => {}
+            }
+            // value - coefficient * t <= max_value
+            if let Some(max) = self.max_value {
+                let excess = value - max.into();
+                match coefficient.cmp(&zero) {
+                    Ordering::Greater => low = low.max((excess / coefficient).ceil()),
+                    Ordering::Less => high = Some(min_bound(high, (excess / coefficient).floor())),
+                    Ordering::Equal if excess > zero => return None,
+                    _ => {}
+                }
+            }
+        }
+
+        let (start, step) = math::crt(&congruences)?;
+        let slope = echelon.rows
+            .iter()
+            .zip(echelon.pivots.iter())
+            .fold(Fraction::from(self.costs[free]), |slope, ((coefficients, _), &pivot)| {
+                slope - coefficients[free] * self.costs[pivot].into()
+            });
+
+        // the cheapest end of the range, when the cost drops as `t` grows
+        // without an upper end, there is no cheapest solution
+        let t = if slope >= Fraction::from(0) {
+            low + (start - low).rem_euclid(step)
+        } else {
+            let high = high?;
+            high - (high - start).rem_euclid(step)
+        };
+        if t < low || high.is_some_and(|high| t > high) {
+            return None;
+        }
+
+        self.solution(echelon, Some(free), t)
+    }
+
+    fn solution(&self, echelon: &Echelon, free: Option<usize>, t: i128) -> Option<IntegerSolution> {
+        let mut values = vec![0; self.costs.len()];
+        if let Some(free) = free {
+            values[free] = t;
+        }
+
+        for ((coefficients, value), &pivot) in echelon.rows.iter().zip(echelon.pivots.iter()) {
+            let coefficient = free.map_or(Fraction::from(0), |free| coefficients[free]);
+            values[pivot] = (*value - coefficient * t.into()).to_integer()?;
+        }
+
+        if values.iter().any(|&v| v < 0 || self.max_value.is_some_and(|max| v > max)) {
+            return None;
+        }
+
+        let cost = values.iter().zip(self.costs.iter()).map(|(v, c)| v * c).sum();
+        Some(IntegerSolution { values, cost })
+    }
+
+    // largest value of `variable` allowed by `max_value` or by an equation
+    // where every coefficient is non-negative
+    fn bound(&self, variable: usize) -> Option<i128> {
+        self.equations
+            .iter()
+            .filter(|(coefficients, value)| {
+                coefficients[variable] > 0 && *value >= 0 && coefficients.iter().all(|&c| c >= 0)
+            })
+            .map(|(coefficients, value)| value / coefficients[variable])
+            .chain(self.max_value)
+            .min()
+    }
+}
+
+fn min_bound(bound: Option<i128>, value: i128) -> i128 {
+    bound.map_or(value, |bound| bound.min(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claw_machine(a: (i128, i128), b: (i128, i128), prize: (i128, i128)) -> IntegerSystem {
+        IntegerSystem::new(&[3, 1])
+            .equation(&[a.0, b.0], prize.0)
+            .equation(&[a.1, b.1], prize.1)
+    }
+
+    #[test]
+    fn test_unique_solution() {
+        let solution = claw_machine((94, 34), (22, 67), (8400, 5400)).cheapest();
+        assert_eq!(solution, Some(IntegerSolution { values: vec![80, 40], cost: 280 }));
+
+        assert_eq!(claw_machine((26, 66), (67, 21), (12748, 12176)).cheapest(), None);
+        assert_eq!(
+            claw_machine((94, 34), (22, 67), (8400, 5400)).with_max_value(50).cheapest(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_collinear_buttons() {
+        // 4a + 6b = 22 has (4, 1) and (1, 3)
+        let solution = claw_machine((4, 4), (6, 6), (22, 22)).cheapest().unwrap();
+        assert_eq!((solution.values, solution.cost), (vec![1, 3], 6));
+
+        // A moves four times as far for three times the price, so it is preferred
+        let solution = claw_machine((4, 8), (1, 2), (10_000_000_000_001, 20_000_000_000_002)).cheapest().unwrap();
+        assert_eq!(solution.values, vec![2_500_000_000_000, 1]);
+
+        assert_eq!(claw_machine((2, 2), (4, 4), (7, 7)).cheapest(), None);
+        assert_eq!(claw_machine((2, 2), (4, 4), (8, 9)).cheapest(), None);
+    }
+
+    #[test]
+    fn test_free_variable_with_max_value() {
+        // a + b = 10 with a cheaper b, which may not go above 4
+        let system = IntegerSystem::new(&[5, 1]).equation(&[1, 1], 10);
+        assert_eq!(system.cheapest().map(|s| s.values), Some(vec![0, 10]));
+        assert_eq!(system.with_max_value(4).cheapest(), None);
+
+        let system = IntegerSystem::new(&[5, 1]).equation(&[1, 1], 10).with_max_value(7);
+        assert_eq!(system.cheapest().map(|s| s.values), Some(vec![3, 7]));
+    }
+
+    #[test]
+    fn test_larger_systems() {
+        // three variables, two equations
+        let solution = IntegerSystem::new(&[1, 1, 1])
+            .equation(&[1, 1, 0], 5)
+            .equation(&[0, 1, 1], 7)
+            .cheapest()
+            .unwrap();
+        assert_eq!((solution.values, solution.cost), (vec![0, 5, 2], 7));
+
+        // two free variables, bounded by the equation itself
+        let solution = IntegerSystem::new(&[4, 3, 1])
+            .equation(&[3, 5, 7], 29)
+            .cheapest()
+            .unwrap();
+        assert_eq!((solution.values, solution.cost), (vec![1, 1, 3], 10));
+
+        // redundant and contradicting equations
+        let system = IntegerSystem::new(&[1, 1]).equation(&[1, 2], 4).equation(&[2, 4], 8);
+        assert_eq!(system.clone().cheapest().map(|s| s.cost), Some(2));
+        assert_eq!(system.equation(&[1, 0], 1).cheapest(), None);
+    }
+
+    #[test]
+    fn test_unbounded() {
+        // a - b = 2 gets cheaper the larger both are
+        assert_eq!(IntegerSystem::new(&[1, -2]).equation(&[1, -1], 2).cheapest(), None);
+        let system = IntegerSystem::new(&[1, -2]).equation(&[1, -1], 2).with_max_value(5);
+        assert_eq!(system.cheapest().map(|s| s.values), Some(vec![5, 3]));
+
+        // two free variables with a negative coefficient bound nothing
+        let system = IntegerSystem::new(&[1, 1, 1]).equation(&[1, -1, 1], 3);
+        assert_eq!(system.cheapest(), None);
+        assert_eq!(system.with_max_value(3).cheapest().map(|s| s.cost), Some(3));
+    }
+}
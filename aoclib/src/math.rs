@@ -0,0 +1,329 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{Integer, Signed};
+
+/// Greatest common divisor, never negative. `gcd(0, 0)` is 0.
+pub fn gcd<N: Integer>(a: N, b: N) -> N {
+    let (mut a, mut b) = (a.distance(N::ZERO), b.distance(N::ZERO));
+    while b != N::ZERO {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Least common multiple, never negative. Panics on overflow, see `checked_lcm`.
+pub fn lcm<N: Integer>(a: N, b: N) -> N {
+    checked_lcm(a, b).expect("lcm overflow")
+}
+
+/// Least common multiple, `None` when it does not fit in `N`.
+pub fn checked_lcm<N: Integer>(a: N, b: N) -> Option<N> {
+    if a == N::ZERO || b == N::ZERO {
+        return Some(N::ZERO);
+    }
+
+    (a.distance(N::ZERO) / gcd(a, b)).checked_mul(b.distance(N::ZERO))
+}
+
+/// `(g, x, y)` with `a * x + b * y = g`, where `g` is `gcd(a, b)`.
+pub fn extended_gcd<N: Signed>(a: N, b: N) -> (N, N, N) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (N::ONE, N::ZERO);
+    let (mut old_y, mut y) = (N::ZERO, N::ONE);
+
+    while r != N::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < N::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `x` in `0..m` with `a * x ≡ 1 (mod m)`, exists only when `a` and `m` are coprime.
+pub fn mod_inverse<N: Signed>(a: N, m: N) -> Option<N> {
+    let (g, x, _) = extended_gcd(a, m);
+
+    (g == N::ONE).then(|| modulo(x, m))
+}
+
+/// Smallest non-negative `x` with `x ≡ r (mod m)` for every `(r, m)`, together with
+/// the lcm of the moduli, which have to be positive but not necessarily coprime.
+/// `None` when the congruences contradict each other. Panics on overflow, see `checked_crt`.
+pub fn crt<N: Signed>(congruences: &[(N, N)]) -> Option<(N, N)> {
+    solve_crt(congruences).expect("crt overflow")
+}
+
+/// Like `crt`, but also `None` when an intermediate value does not fit in `N`.
+pub fn checked_crt<N: Signed>(congruences: &[(N, N)]) -> Option<(N, N)> {
+    solve_crt(congruences).flatten()
+}
+
+// the outer `None` is an overflow, the inner one a contradiction
+fn solve_crt<N: Signed>(congruences: &[(N, N)]) -> Option<Option<(N, N)>> {
+    let (mut r1, mut m1) = (N::ZERO, N::ONE);
+
+    for &(r2, m2) in congruences {
+        assert!(m2 > N::ZERO, "crt needs positive moduli");
+
+        let r2 = modulo(r2, m2);
+        let (g, p, _) = extended_gcd(m1, m2);
+        let diff = r2.checked_sub(r1)?;
+        if diff % g != N::ZERO {
+            return Some(None);
+        }
+
+        // r1 + m1 * k solves both, with k = diff / g * p (mod m2 / g)
+        let step = m2 / g;
+        let k = modulo(modulo(diff / g, step).checked_mul(modulo(p, step))?, step);
+        let modulus = (m1 / g).checked_mul(m2)?;
+
+        r1 = modulo(r1.checked_add(m1.checked_mul(k)?)?, modulus);
+        m1 = modulus;
+    }
+
+    Some(Some((r1, m1)))
+}
+
+// remainder in `0..m` for a positive `m`
+fn modulo<N: Integer>(a: N, m: N) -> N {
+    let r = a % m;
+    if r < N::ZERO { r + m } else { r }
+}
+
+/// Exact fraction, kept in lowest terms with a positive denominator. Operators panic
+/// on overflow, the `checked_*` methods return `None` instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational<N = i64> {
+    numer: N,
+    denom: N,
+}
+
+impl<N: Signed> Rational<N> {
+    /// Panics when `denom` is zero.
+    pub fn new(numer: N, denom: N) -> Self {
+        assert!(denom != N::ZERO, "rational with a zero denominator");
+
+        let g = gcd(numer, denom);
+        let (numer, denom) = (numer / g, denom / g);
+        if denom < N::ZERO {
+            Rational { numer: -numer, denom: -denom }
+        } else {
+            Rational { numer, denom }
+        }
+    }
+
+    pub fn numer(&self) -> N {
+        self.numer
+    }
+
+    pub fn denom(&self) -> N {
+        self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == N::ONE
+    }
+
+    /// The value when it is a whole number.
+    pub fn to_integer(&self) -> Option<N> {
+        self.is_integer().then_some(self.numer)
+    }
+
+    /// Largest whole number not above the value.
+    pub fn floor(&self) -> N {
+        (self.numer - modulo(self.numer, self.denom)) / self.denom
+    }
+
+    /// Smallest whole number not below the value.
+    pub fn ceil(&self) -> N {
+        -(-*self).floor()
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let g = gcd(self.denom, rhs.denom);
+        let numer = self.numer.checked_mul(rhs.denom / g)?
+            .checked_add(rhs.numer.checked_mul(self.denom / g)?)?;
+
+        Some(Rational::new(numer, (self.denom / g).checked_mul(rhs.denom)?))
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(-rhs)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        // reducing crosswise first keeps the products small
+        let g1 = gcd(self.numer, rhs.denom).max(N::ONE);
+        let g2 = gcd(rhs.numer, self.denom).max(N::ONE);
+
+        Some(Rational::new(
+            (self.numer / g1).checked_mul(rhs.numer / g2)?,
+            (self.denom / g2).checked_mul(rhs.denom / g1)?,
+        ))
+    }
+
+    /// `None` also when dividing by zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.numer == N::ZERO {
+            return None;
+        }
+
+        self.checked_mul(Rational::new(rhs.denom, rhs.numer))
+    }
+}
+
+impl<N: Signed> From<N> for Rational<N> {
+    fn from(value: N) -> Self {
+        Rational { numer: value, denom: N::ONE }
+    }
+}
+
+impl<N: Signed> Add for Rational<N> {
+    type Output = Rational<N>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("rational overflow")
+    }
+}
+
+impl<N: Signed> Sub for Rational<N> {
+    type Output = Rational<N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs).expect("rational overflow")
+    }
+}
+
+impl<N: Signed> Mul for Rational<N> {
+    type Output = Rational<N>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs).expect("rational overflow")
+    }
+}
+
+impl<N: Signed> Div for Rational<N> {
+    type Output = Rational<N>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        assert!(rhs.numer != N::ZERO, "rational division by zero");
+        self.checked_div(rhs).expect("rational overflow")
+    }
+}
+
+impl<N: Signed> Neg for Rational<N> {
+    type Output = Rational<N>;
+
+    fn neg(self) -> Self::Output {
+        Rational { numer: -self.numer, denom: self.denom }
+    }
+}
+
+impl<N: Signed> Ord for Rational<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // denominators are positive, so cross multiplying keeps the order
+        let left = self.numer.checked_mul(other.denom).expect("rational overflow");
+        let right = other.numer.checked_mul(self.denom).expect("rational overflow");
+        left.cmp(&right)
+    }
+}
+
+impl<N: Signed> PartialOrd for Rational<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N: Signed + Display> Display for Rational<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0i32, 0), 0);
+        assert_eq!(lcm(4u64, 6), 12);
+        assert_eq!(lcm(-4i32, 6), 12);
+        assert_eq!([23u64, 19, 13, 17].into_iter().fold(1, lcm), 96577);
+
+        assert_eq!(checked_lcm(u8::MAX, 2), None);
+        assert_eq!(checked_lcm(0u8, 7), Some(0));
+    }
+
+    #[test]
+    fn test_extended_gcd_and_inverse() {
+        let (g, x, y) = extended_gcd(240i64, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+
+        assert_eq!(extended_gcd(-6i32, 4).0, 2);
+        assert_eq!(mod_inverse(3i32, 11), Some(4));
+        assert_eq!(mod_inverse(-3i32, 11), Some(7));
+        assert_eq!(mod_inverse(4i32, 10), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli sharing a factor
+        assert_eq!(crt(&[(3i64, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1i32, 101), (0, 103)]), Some((5150, 10403)));
+        assert_eq!(crt::<i32>(&[]), Some((0, 1)));
+
+        assert_eq!(checked_crt(&[(1i8, 11), (2, 13)]), None);
+        assert_eq!(checked_crt(&[(1i16, 11), (2, 13)]), Some((67, 143)));
+    }
+
+    #[test]
+    fn test_rational_arithmetic() {
+        let half = Rational::new(1i64, 2);
+        let third = Rational::new(-2i64, -6);
+
+        assert_eq!(third, Rational::new(1, 3));
+        assert_eq!(half + third, Rational::new(5, 6));
+        assert_eq!(half - third, Rational::new(1, 6));
+        assert_eq!(half * third, Rational::new(1, 6));
+        assert_eq!(half / third, Rational::new(3, 2));
+        assert_eq!(-half, Rational::new(1, -2));
+        assert!(third < half && -half < third);
+
+        assert_eq!((half + half).to_integer(), Some(1));
+        assert_eq!(half.to_integer(), None);
+        assert_eq!((Rational::new(-7i64, 2).floor(), Rational::new(-7i64, 2).ceil()), (-4, -3));
+        assert_eq!((Rational::from(5i64).floor(), Rational::from(5i64).ceil()), (5, 5));
+        assert_eq!(Rational::new(6i64, -4).to_string(), "-3/2");
+        assert_eq!(Rational::from(7i64).to_string(), "7");
+    }
+
+    #[test]
+    fn test_rational_checked() {
+        let big = Rational::new(i64::MAX, 2);
+
+        assert_eq!(big.checked_mul(Rational::from(3)), None);
+        assert_eq!(big.checked_mul(Rational::new(2, 3)), Some(Rational::new(i64::MAX, 3)));
+        assert_eq!(Rational::new(1i64, 2).checked_div(Rational::from(0)), None);
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// Cache of computed values counting its hits and misses, optionally holding
+/// at most `capacity` entries.
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    capacity: Option<usize>,
+    // keys in insertion order, only kept for a bounded memo
+    order: VecDeque<K>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K, V> Default for Memo<K, V>
+    where K: Hash + Eq + Clone,
+          V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Memo<K, V>
+    where K: Hash + Eq + Clone,
+          V: Clone,
+{
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            capacity: None,
+            order: VecDeque::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Memo dropping its oldest entry when a new one does not fit.
+    pub fn bounded(capacity: usize) -> Self {
+        Memo {
+            capacity: Some(capacity),
+            ..Self::new()
+        }
+    }
+
+    /// Cached value of `key`, computed by `f` on a miss.
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> V
+        where F: FnOnce() -> V
+    {
+        if let Some(value) = self.lookup(&key) {
+            return value;
+        }
+
+        let value = f();
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// Turns a recursive function into a memoised one. `f` gets itself as the first
+    /// argument and calls it for the subproblems, which are cached too.
+    ///
+    /// ```
+    /// use aoclib::Memo;
+    ///
+    /// let mut fib = Memo::new().wrap(|fib, n: u64| if n < 2 { n } else { fib(n - 1) + fib(n - 2) });
+    /// assert_eq!(fib.call(90), 2880067194370816120);
+    /// ```
+    pub fn wrap<F>(self, f: F) -> Memoized<K, V, F>
+        where F: Fn(&mut dyn FnMut(K) -> V, K) -> V
+    {
+        Memoized { memo: self, f }
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Number of cached values.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    fn lookup(&mut self, key: &K) -> Option<V> {
+        let value = self.cache.get(key).cloned();
+        match value {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+
+        value
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if let Some(capacity) = self.capacity {
+            if capacity == 0 {
+                return;
+            }
+            // a recursive call may have cached the key in the meantime
+            if !self.cache.contains_key(&key) {
+                if self.cache.len() == capacity {
+                    if let Some(oldest) = self.order.pop_front() {
+                        self.cache.remove(&oldest);
+                    }
+                }
+                self.order.push_back(key.clone());
+            }
+        }
+
+        self.cache.insert(key, value);
+    }
+}
+
+/// Recursive function with its own `Memo`, see `Memo::wrap`.
+pub struct Memoized<K, V, F> {
+    memo: Memo<K, V>,
+    f: F,
+}
+
+impl<K, V, F> Memoized<K, V, F>
+    where K: Hash + Eq + Clone,
+          V: Clone,
+          F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn call(&mut self, key: K) -> V {
+        Self::call_with(&self.f, &mut self.memo, key)
+    }
+
+    pub fn memo(&self) -> &Memo<K, V> {
+        &self.memo
+    }
+
+    fn call_with(f: &F, memo: &mut Memo<K, V>, key: K) -> V {
+        if let Some(value) = memo.lookup(&key) {
+            return value;
+        }
+
+        let value = f(&mut |subproblem| Self::call_with(f, memo, subproblem), key.clone());
+        memo.insert(key, value.clone());
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_or_insert_with() {
+        let mut memo = Memo::new();
+
+        assert_eq!(memo.get_or_insert_with("a", || 1), 1);
+        assert_eq!(memo.get_or_insert_with("a", || 2), 1);
+        assert_eq!((memo.hits(), memo.misses(), memo.len()), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_wrap_recursive() {
+        let mut fib = Memo::new().wrap(|fib, n: u64| if n < 2 { n } else { fib(n - 1) + fib(n - 2) });
+
+        assert_eq!(fib.call(90), 2880067194370816120);
+        // every n is computed once, fib(n - 2) is reused from n = 3 on
+        assert_eq!((fib.memo().misses(), fib.memo().hits()), (91, 88));
+
+        fib.call(90);
+        assert_eq!(fib.memo().hits(), 89);
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut memo = Memo::bounded(2);
+        memo.get_or_insert_with(1, || 'a');
+        memo.get_or_insert_with(2, || 'b');
+        memo.get_or_insert_with(3, || 'c');
+        assert_eq!(memo.len(), 2);
+
+        // 1 was the oldest, so it is computed again
+        assert_eq!(memo.get_or_insert_with(1, || 'z'), 'z');
+        assert_eq!(memo.get_or_insert_with(3, || 'z'), 'c');
+        assert_eq!(memo.misses(), 4);
+    }
+
+    #[test]
+    fn test_bounded_recursion_stays_correct() {
+        let mut fib = Memo::bounded(3).wrap(|fib, n: u64| if n < 2 { n } else { fib(n - 1) + fib(n - 2) });
+
+        assert_eq!(fib.call(50), 12586269025);
+        assert!(fib.memo().len() <= 3);
+    }
+}
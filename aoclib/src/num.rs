@@ -0,0 +1,82 @@
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    hash::Hash,
+    ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign},
+};
+
+/// Primitive integer usable as a coordinate of `Vec2` and `Vec3`.
+pub trait Integer:
+    Copy + Debug + Default + Hash + Ord
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+    + Div<Output = Self> + Rem<Output = Self>
+    + AddAssign + SubAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    /// Absolute difference, never overflows for unsigned types.
+    fn distance(self, other: Self) -> Self {
+        if self > other { self - other } else { other - self }
+    }
+
+    /// -1, 0 or 1 following the sign, unsigned values give 0 or 1.
+    fn signum(self) -> Self {
+        match self.cmp(&Self::ZERO) {
+            Ordering::Greater => Self::ONE,
+            Ordering::Equal => Self::ZERO,
+            Ordering::Less => Self::ZERO - Self::ONE,
+        }
+    }
+}
+
+/// Integer with negative values, needed for rotations and negation.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($($t:ty)*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        })*
+    };
+}
+
+impl_integer!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+impl Signed for i8 {}
+impl Signed for i16 {}
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_and_signum() {
+        assert_eq!(3u8.distance(10), 7);
+        assert_eq!((-4i64).distance(2), 6);
+        assert_eq!(Integer::signum(-7i32), -1);
+        assert_eq!(Integer::signum(0u32), 0);
+        assert_eq!(Integer::signum(9usize), 1);
+    }
+}
@@ -0,0 +1,474 @@
+use std::{fmt::Display, str::FromStr};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till1},
+    character::complete::{char, digit1, line_ending, multispace0, one_of, space0},
+    combinator::{all_consuming, cut, eof, map, not, opt, recognize},
+    error::{ContextError, ErrorKind, FromExternalError},
+    multi::{many1_count, separated_list1},
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
+    Parser,
+};
+
+use crate::{Grid, Vec2};
+
+/// Error of a puzzle input parser, pointing at the offending token.
+///
+/// `line` and `column` are 1-based, `found` is the token (or a short
+/// description like `end of line`) that did not match `expected`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// `nom` error of the combinators below, it knows what was expected once a
+/// parser is wrapped in `expect`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Expected<'a> {
+    pub input: &'a str,
+    pub kind: ErrorKind,
+    pub label: Option<String>,
+}
+
+pub type IResult<'a, O> = nom::IResult<&'a str, O, Expected<'a>>;
+
+impl<'a> Expected<'a> {
+    // the innermost label is the most precise one, outer labels only fill in a missing one
+    fn labelled(self, input: &'a str, label: &str) -> Self {
+        match self.label {
+            Some(_) => self,
+            None => Expected { input, kind: self.kind, label: Some(label.to_string()) },
+        }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Expected<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Expected { input, kind, label: None }
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a> ContextError<&'a str> for Expected<'a> {
+    fn add_context(input: &'a str, context: &'static str, other: Self) -> Self {
+        other.labelled(input, context)
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for Expected<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _: E) -> Self {
+        Expected { input, kind, label: None }
+    }
+}
+
+impl<'a> From<nom::error::Error<&'a str>> for Expected<'a> {
+    fn from(err: nom::error::Error<&'a str>) -> Self {
+        Expected { input: err.input, kind: err.code, label: None }
+    }
+}
+
+const MAX_FOUND_LEN: usize = 20;
+
+impl ParseError {
+    /// Builds an error located at `token`, which should be a slice of `input`.
+    pub fn at(input: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = offset_in(input, token);
+        let (line, column) = line_column(input, offset);
+
+        ParseError {
+            line,
+            column,
+            expected: expected.into(),
+            found: describe_found(&input[offset..], token),
+        }
+    }
+
+    /// Converts a `nom` error, `input` is the text handed to the top level parser.
+    pub fn from_nom<'a, E: Into<Expected<'a>>>(input: &str, err: nom::Err<E>) -> Self {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                let e = e.into();
+                let expected = e.label.unwrap_or_else(|| describe_kind(e.kind));
+                ParseError::at(input, first_token(e.input), expected)
+            },
+            nom::Err::Incomplete(_) => {
+                ParseError::at(input, &input[input.len()..], "more input")
+            },
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `token` (a slice of `input`) with `FromStr`.
+pub fn parse_token<T: FromStr>(input: &str, token: &str, expected: &str) -> ParseResult<T> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::at(input, token, expected))
+}
+
+/// Runs `parser` over the whole `input`, only trailing whitespace may be left.
+pub fn parse_all<'a, O, P>(input: &'a str, parser: P) -> ParseResult<O>
+    where P: Parser<&'a str, O, Expected<'a>>
+{
+    all_consuming(terminated(parser, multispace0))(input)
+        .map(|(_, output)| output)
+        .map_err(|err| ParseError::from_nom(input, err))
+}
+
+/// Reports `label` as expected when `parser` fails without a more precise label,
+/// like `expect("gate", alt((tag("AND"), tag("OR"))))`.
+pub fn expect<'a, O, P>(label: impl Into<String>, mut parser: P) -> impl FnMut(&'a str) -> IResult<'a, O>
+    where P: Parser<&'a str, O, Expected<'a>>
+{
+    let label = label.into();
+    move |input: &'a str| parser.parse(input).map_err(|err| err.map(|e| e.labelled(input, &label)))
+}
+
+/// Exact `text`, reported as expected in backticks when missing.
+pub fn literal<'a>(text: &'a str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    expect(format!("`{}`", text), tag(text))
+}
+
+/// Integer of any width with an optional `+` or `-` sign.
+pub fn integer<N: FromStr>(input: &str) -> IResult<'_, N> {
+    let (rest, digits) = recognize(pair(opt(one_of("+-")), digit1))(input)
+        .map_err(|_: nom::Err<Expected>| error(input, ErrorKind::Digit))?;
+
+    match digits.parse() {
+        Ok(value) => Ok((rest, value)),
+        // too many digits for `N`, or a negative unsigned number
+        Err(_) => Err(error(input, ErrorKind::TooLarge)),
+    }
+}
+
+/// One or more integers with `separator` between them, like `3,4,5` or `3 -4  5`.
+pub fn numbers<'a, N, S, O>(separator: S) -> impl FnMut(&'a str) -> IResult<'a, Vec<N>>
+    where N: FromStr,
+          S: Parser<&'a str, O, Expected<'a>>,
+{
+    separated_list1(separator, integer)
+}
+
+/// Pair of integers like `5,4` or `-3, 7`.
+pub fn vec2<N: FromStr>(input: &str) -> IResult<'_, Vec2<N>> {
+    expect(
+        "coordinates like `5,4`",
+        map(
+            separated_pair(expect("x coordinate", integer), pair(char(','), space0), expect("y coordinate", integer)),
+            |(x, y)| Vec2 { x, y },
+        ),
+    )(input)
+}
+
+/// Value preceded by its label, with an optional `:` or `=` in between,
+/// like `Button A: X+94, Y+34`, `X=8400` or `p=0,4`.
+pub fn labelled<'a, O, P>(label: &'a str, value: P) -> impl FnMut(&'a str) -> IResult<'a, O>
+    where P: Parser<&'a str, O, Expected<'a>>
+{
+    preceded(tuple((literal(label), opt(one_of(":=")), space0)), value)
+}
+
+/// One `parser` match per line, up to a blank line or the end of input. The line
+/// ending after the last match is not consumed.
+pub fn lines<'a, O, P>(parser: P) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>>
+    where P: Parser<&'a str, O, Expected<'a>>
+{
+    // a line that is not blank has to match, so its error is reported
+    let not_blank = expect("at least one line", not(pair(space0, alt((line_ending, eof)))));
+    separated_list1(line_ending, preceded(not_blank, cut(parser)))
+}
+
+/// Line ending followed by one or more blank lines.
+pub fn paragraph_break(input: &str) -> IResult<'_, &str> {
+    recognize(pair(line_ending, many1_count(pair(space0, line_ending))))(input)
+}
+
+/// One `parser` match per paragraph, paragraphs are separated by blank lines.
+pub fn paragraphs<'a, O, P>(parser: P) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>>
+    where P: Parser<&'a str, O, Expected<'a>>
+{
+    let not_empty = expect("at least one block", not(preceded(multispace0, eof)));
+    separated_list1(paragraph_break, preceded(not_empty, cut(parser)))
+}
+
+/// Grid of characters up to a blank line or the end of input. `cell` returns
+/// `None` for characters that are not allowed, every row needs as many cells as the first one.
+pub fn grid<'a, T, F>(cell: F) -> impl FnMut(&'a str) -> IResult<'a, Grid<T>>
+    where F: Fn(char) -> Option<T>
+{
+    move |input: &'a str| {
+        let (rest, rows) = lines(take_till1(|c| c == '\n' || c == '\r'))(input)?;
+
+        let mut data = Vec::with_capacity(input.len() - rest.len());
+        let mut width = 0;
+        for (row_no, row) in rows.into_iter().enumerate() {
+            let row_start = data.len();
+            for (pos, c) in row.char_indices() {
+                let value = cell(c)
+                    .ok_or_else(|| failure(&row[pos..pos + c.len_utf8()], ErrorKind::MapOpt))?;
+                data.push(value);
+            }
+
+            let cells = data.len() - row_start;
+            if row_no == 0 {
+                width = cells;
+            } else if cells != width {
+                let end = row.char_indices().nth(width).map_or(row.len(), |(pos, _)| pos);
+                return Err(failure(&row[end..], ErrorKind::Count));
+            }
+        }
+
+        Ok((rest, Grid::new_from_raw(data, width as i32)))
+    }
+}
+
+fn error(input: &str, kind: ErrorKind) -> nom::Err<Expected<'_>> {
+    nom::Err::Error(Expected { input, kind, label: None })
+}
+
+fn failure(input: &str, kind: ErrorKind) -> nom::Err<Expected<'_>> {
+    nom::Err::Failure(Expected { input, kind, label: None })
+}
+
+fn offset_in(input: &str, token: &str) -> usize {
+    let start = input.as_ptr() as usize;
+    let token_start = token.as_ptr() as usize;
+
+    if token_start >= start && token_start + token.len() <= start + input.len() {
+        token_start - start
+    } else {
+        input.find(token).unwrap_or(0)
+    }
+}
+
+fn line_column(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+
+    (line, before[line_start..].chars().count() + 1)
+}
+
+fn describe_kind(kind: nom::error::ErrorKind) -> String {
+    use nom::error::ErrorKind;
+
+    match kind {
+        ErrorKind::Tag => "literal text".to_string(),
+        ErrorKind::Eof => "end of input".to_string(),
+        ErrorKind::CrLf => "line ending".to_string(),
+        ErrorKind::Alpha => "letter".to_string(),
+        ErrorKind::AlphaNumeric => "letter or digit".to_string(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace".to_string(),
+        ErrorKind::Verify => "valid value".to_string(),
+        ErrorKind::OneOf => "one of the allowed characters".to_string(),
+        ErrorKind::Char => "character".to_string(),
+        ErrorKind::TooLarge => "number in range".to_string(),
+        ErrorKind::MapOpt => "known cell".to_string(),
+        ErrorKind::Count => "row as long as the first one".to_string(),
+        kind => format!("{:?}", kind).to_lowercase(),
+    }
+}
+
+fn first_token(rest: &str) -> &str {
+    let end = rest
+        .find(|c: char| c.is_whitespace())
+        .unwrap_or(rest.len());
+
+    &rest[..end]
+}
+
+fn describe_found(rest: &str, token: &str) -> String {
+    let token = token.lines().next().unwrap_or("");
+
+    if !token.is_empty() {
+        let shortened: String = token.chars().take(MAX_FOUND_LEN).collect();
+        if shortened.len() < token.len() {
+            format!("`{}...`", shortened)
+        } else {
+            format!("`{}`", shortened)
+        }
+    } else if rest.is_empty() {
+        "end of input".to_string()
+    } else if rest.starts_with('\n') || rest.starts_with("\r\n") {
+        "end of line".to_string()
+    } else {
+        format!("`{}`", first_token(rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "1,2\n3,x4\n";
+
+    #[test]
+    fn test_error_position() {
+        let second_line = INPUT.lines().nth(1).unwrap();
+        let token = second_line.split(',').nth(1).unwrap();
+
+        let err = ParseError::at(INPUT, token, "number");
+
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.found, "`x4`");
+        assert_eq!(err.to_string(), "line 2, column 3: expected number, found `x4`");
+    }
+
+    #[test]
+    fn test_error_at_end_of_line_and_input() {
+        let first_line = INPUT.lines().next().unwrap();
+
+        let err = ParseError::at(INPUT, &first_line[3..], "`,`");
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(err.found, "end of line");
+
+        let err = ParseError::at(INPUT, &INPUT[INPUT.len()..], "number");
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.found, "end of input");
+    }
+
+    #[test]
+    fn test_parse_token() {
+        let second_line = INPUT.lines().nth(1).unwrap();
+
+        assert_eq!(parse_token::<i32>(INPUT, &second_line[..1], "number"), Ok(3));
+
+        let err = parse_token::<i32>(INPUT, &second_line[2..], "number").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn test_from_nom() {
+        use nom::{character::complete::digit1, bytes::complete::tag, sequence::tuple};
+
+        let input = "12-ab";
+        let err = tuple((digit1::<&str, nom::error::Error<&str>>, tag("-"), digit1))(input)
+            .map_err(|e| ParseError::from_nom(input, e))
+            .unwrap_err();
+
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(err.expected, "digit");
+        assert_eq!(err.found, "`ab`");
+    }
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(integer::<i8>("-128,"), Ok((",", -128)));
+        assert_eq!(integer::<u64>("+42"), Ok(("", 42)));
+        assert_eq!(integer::<i128>("170141183460469231731687303715884105727"), Ok(("", i128::MAX)));
+
+        let err = parse_all("7\n300", lines(integer::<u8>)).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: expected number in range, found `300`");
+
+        let err = parse_all("-5", integer::<u32>).unwrap_err();
+        assert_eq!(err.expected, "number in range");
+        assert!(integer::<i32>("-x").is_err());
+    }
+
+    #[test]
+    fn test_numbers_and_vec2() {
+        assert_eq!(parse_all("1,-2,3", numbers::<i32, _, _>(char(','))), Ok(vec![1, -2, 3]));
+        assert_eq!(parse_all("4  5 -6\n", numbers::<i64, _, _>(nom::character::complete::space1)), Ok(vec![4, 5, -6]));
+        assert_eq!(parse_all("79, 98", numbers::<u64, _, _>(tag(", "))), Ok(vec![79, 98]));
+
+        assert_eq!(parse_all("5,4\n-1, 2", lines(vec2::<i32>)), Ok(vec![(5, 4).into(), (-1, 2).into()]));
+
+        let err = parse_all("5,4\n4;2", lines(vec2::<i32>)).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: expected coordinates like `5,4`, found `4;2`");
+
+        let err = parse_all("5,4\n4,-", lines(vec2::<i32>)).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 3: expected y coordinate, found `-`");
+    }
+
+    #[test]
+    fn test_labelled() {
+        let mut button = labelled(
+            "Button A",
+            separated_pair(labelled("X", integer::<i64>), tag(", "), labelled("Y", integer::<i64>)),
+        );
+        assert_eq!(button("Button A: X+94, Y-34"), Ok(("", (94, -34))));
+
+        let mut prize = labelled("Prize", separated_pair(labelled("X", integer), literal(", "), labelled("Y", integer)));
+        assert_eq!(prize("Prize: X=8400, Y=5400"), Ok(("", (8400u32, 5400u32))));
+
+        assert_eq!(labelled("p", vec2::<i32>)("p=0,4 v=3,-3"), Ok((" v=3,-3", (0, 4).into())));
+
+        let err = parse_all("Price: X=1, Y=2", &mut prize).map(|_| ()).unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: expected `Prize`, found `Price:`");
+
+        let err = parse_all("Prize: X=1; Y=2", &mut prize).map(|_| ()).unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 11: expected `, `, found `;`");
+    }
+
+    #[test]
+    fn test_expect() {
+        let mut gate = expect("gate `AND` or `OR`", alt((tag("AND"), tag("OR"))));
+        assert_eq!(gate("OR x"), Ok((" x", "OR")));
+
+        let err = parse_all("XOR", &mut gate).unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: expected gate `AND` or `OR`, found `XOR`");
+
+        // the innermost label wins
+        let err = parse_all("p=1;2", expect("robot", labelled("p", vec2::<i32>))).unwrap_err();
+        assert_eq!(err.expected, "coordinates like `5,4`");
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let input = "1\n2\n\n3\n  \n\n4\n\n";
+
+        assert_eq!(parse_all(input, paragraphs(lines(integer::<u8>))), Ok(vec![vec![1, 2], vec![3], vec![4]]));
+
+        let err = parse_all("1\n\nx\n", paragraphs(lines(integer::<u8>))).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let err = parse_all("", lines(integer::<u8>)).unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: expected at least one line, found end of input");
+
+        let err = parse_all("\n\n", paragraphs(lines(integer::<u8>))).unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: expected at least one block, found end of line");
+    }
+
+    #[test]
+    fn test_grid() {
+        let cell = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+
+        let (rest, walls) = grid(cell)("#.#\n..#\n\n<>").unwrap();
+        assert_eq!(rest, "\n\n<>");
+        assert_eq!(walls.size(), (3, 2).into());
+        assert_eq!(walls.get_at(&(2, 1).into()), Some(&true));
+
+        let err = parse_all("#.#\n.x#", grid(cell)).map(|_| ()).unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 2: expected known cell, found `x`");
+
+        let err = parse_all("#.#\n.#", grid(cell)).map(|_| ()).unwrap_err();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 3, "end of input"));
+    }
+}
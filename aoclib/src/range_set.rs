@@ -0,0 +1,283 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use crate::Integer;
+
+/// Set of integers stored as disjoint half-open ranges. Overlapping and touching
+/// ranges are merged, so `0..3` and `3..5` are kept as `0..5`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RangeSet<N> {
+    // start -> end of every range, no two of them overlap or touch
+    ranges: BTreeMap<N, N>,
+}
+
+impl<N: Integer> RangeSet<N> {
+    pub fn new() -> Self {
+        RangeSet { ranges: BTreeMap::new() }
+    }
+
+    /// Adds `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<N>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let (mut start, mut end) = (range.start, range.end);
+        if let Some((&s, &e)) = self.ranges.range(..=start).next_back() {
+            if e >= start {
+                start = s;
+                end = end.max(e);
+            }
+        }
+
+        let absorbed: Vec<N> = self.ranges.range(start..=end).map(|(&s, _)| s).collect();
+        for s in absorbed {
+            if let Some(e) = self.ranges.remove(&s) {
+                end = end.max(e);
+            }
+        }
+
+        self.ranges.insert(start, end);
+    }
+
+    /// Takes `range` out, splitting the ranges it cuts through.
+    pub fn remove(&mut self, range: Range<N>) {
+        if range.is_empty() {
+            return;
+        }
+
+        if let Some((&s, &e)) = self.ranges.range(..range.start).next_back() {
+            if e > range.start {
+                self.ranges.insert(s, range.start);
+                if e > range.end {
+                    self.ranges.insert(range.end, e);
+                }
+            }
+        }
+
+        let inside: Vec<(N, N)> = self.ranges.range(range.start..range.end).map(|(&s, &e)| (s, e)).collect();
+        for (s, e) in inside {
+            self.ranges.remove(&s);
+            if e > range.end {
+                self.ranges.insert(range.end, e);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: N) -> bool {
+        self.containing(value).is_some_and(|(_, end)| value < end)
+    }
+
+    /// Whether every value of `range` is in the set, always true for an empty range.
+    pub fn contains_range(&self, range: &Range<N>) -> bool {
+        range.is_empty() || self.containing(range.start).is_some_and(|(_, end)| range.end <= end)
+    }
+
+    /// Whether any value of `range` is in the set.
+    pub fn overlaps(&self, range: &Range<N>) -> bool {
+        !range.is_empty()
+            && (self.contains(range.start) || self.ranges.range(range.start..range.end).next().is_some())
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.iter().all(|range| other.contains_range(&range))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut left, mut right) = (self.iter().peekable(), other.iter().peekable());
+        let mut common = vec![];
+
+        while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+            let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+            if start < end {
+                common.push((start, end));
+            }
+
+            // the range ending first cannot overlap anything further
+            if a.end < b.end {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+
+        RangeSet { ranges: common.into_iter().collect() }
+    }
+
+    /// Values of `self` missing from `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut others = other.iter().peekable();
+        let mut rest = vec![];
+
+        for range in self.iter() {
+            let mut start = range.start;
+            while let Some(cut) = others.peek() {
+                if cut.end <= start {
+                    others.next();
+                    continue;
+                }
+                if cut.start >= range.end {
+                    break;
+                }
+
+                if cut.start > start {
+                    rest.push((start, cut.start));
+                }
+                start = cut.end;
+                if cut.end > range.end {
+                    break;
+                }
+                others.next();
+            }
+
+            if start < range.end {
+                rest.push((start, range.end));
+            }
+        }
+
+        RangeSet { ranges: rest.into_iter().collect() }
+    }
+
+    /// Number of disjoint ranges.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn total_length(&self) -> N {
+        self.ranges.iter().fold(N::ZERO, |total, (&start, &end)| total + (end - start))
+    }
+
+    /// Disjoint ranges in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<N>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..end)
+    }
+
+    /// Ranges between consecutive ranges of the set, in increasing order.
+    pub fn gaps(&self) -> impl Iterator<Item = Range<N>> + '_ {
+        self.ranges
+            .iter()
+            .zip(self.ranges.keys().skip(1))
+            .map(|((_, &end), &next)| end..next)
+    }
+
+    fn containing(&self, value: N) -> Option<(N, N)> {
+        self.ranges.range(..=value).next_back().map(|(&start, &end)| (start, end))
+    }
+}
+
+impl<N: Integer> From<Range<N>> for RangeSet<N> {
+    fn from(range: Range<N>) -> Self {
+        let mut set = RangeSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+// sorts once and merges neighbours, much faster than inserting one by one
+impl<N: Integer> FromIterator<Range<N>> for RangeSet<N> {
+    fn from_iter<T: IntoIterator<Item = Range<N>>>(iter: T) -> Self {
+        let mut ranges: Vec<Range<N>> = iter.into_iter().filter(|r| !r.is_empty()).collect();
+        ranges.sort_unstable_by_key(|r| r.start);
+
+        let mut merged: Vec<(N, N)> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some((_, end)) if range.start <= *end => *end = range.end.max(*end),
+                _ => merged.push((range.start, range.end)),
+            }
+        }
+
+        RangeSet { ranges: merged.into_iter().collect() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges<N: Integer>(set: &RangeSet<N>) -> Vec<Range<N>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut set = RangeSet::new();
+        set.insert(10..20);
+        set.insert(0..5);
+        set.insert(5..7);
+        set.insert(30..30);
+        assert_eq!(ranges(&set), [0..7, 10..20]);
+
+        set.insert(6..12);
+        set.insert(15..25);
+        assert_eq!(set, RangeSet::from(0..25));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_remove_splits() {
+        let mut set: RangeSet<i64> = [0..10, 20..30].into_iter().collect();
+
+        set.remove(3..5);
+        set.remove(8..22);
+        set.remove(-5..1);
+        assert_eq!(ranges(&set), [1..3, 5..8, 22..30]);
+
+        set.remove(0..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_containment() {
+        let set: RangeSet<i64> = [2..5, 8..9].into_iter().collect();
+
+        assert!(set.contains(2) && set.contains(4) && set.contains(8));
+        assert!(!set.contains(5) && !set.contains(1) && !set.contains(9));
+        assert!(set.contains_range(&(2..5)) && !set.contains_range(&(4..9)));
+        assert!(set.overlaps(&(0..3)) && set.overlaps(&(6..9)) && !set.overlaps(&(5..8)));
+
+        assert!(RangeSet::from(3..4).is_subset(&set));
+        assert!(!RangeSet::from(3..6).is_subset(&set));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a: RangeSet<i64> = [0..10, 20..30].into_iter().collect();
+        let b: RangeSet<i64> = [5..25, 28..40].into_iter().collect();
+
+        assert_eq!(a.union(&b), RangeSet::from(0..40));
+        assert_eq!(ranges(&a.intersection(&b)), [5..10, 20..25, 28..30]);
+        assert_eq!(ranges(&a.difference(&b)), [0..5, 25..28]);
+        assert_eq!(ranges(&b.difference(&a)), [10..20, 30..40]);
+        assert_eq!(a.difference(&a), RangeSet::new());
+    }
+
+    #[test]
+    fn test_length_and_gaps() {
+        let set: RangeSet<i64> = [0..3, 2..4, 10..12, 20..21].into_iter().collect();
+
+        assert_eq!(set.total_length(), 7);
+        assert_eq!(set.gaps().collect::<Vec<_>>(), [4..10, 12..20]);
+        assert_eq!(RangeSet::<u8>::new().gaps().count(), 0);
+    }
+
+    #[test]
+    fn test_million_ranges() {
+        let evens: RangeSet<u64> = (0..500_000u64).rev().map(|i| 2 * i..2 * i + 1).collect();
+        let odds: RangeSet<u64> = (0..500_000u64).map(|i| 2 * i + 1..2 * i + 2).collect();
+
+        assert_eq!(evens.len(), 500_000);
+        assert_eq!(evens.gaps().count(), 499_999);
+        assert_eq!(evens.union(&odds), RangeSet::from(0..1_000_000));
+        assert!(evens.intersection(&odds).is_empty());
+        assert_eq!(evens.difference(&odds).total_length(), 500_000);
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use crate::{Topology, Vec2};
+
+/// Connected group of grid cells, see `Grid::regions`.
+///
+/// The geometry treats cells as unit squares on a plane, regardless of the grid topology.
+#[derive(Debug)]
+pub struct Region {
+    pub label: usize,
+    cells: HashSet<Vec2>,
+}
+
+impl Region {
+    pub fn new(label: usize, cells: HashSet<Vec2>) -> Self {
+        Region { label, cells }
+    }
+
+    pub fn contains(&self, p: &Vec2) -> bool {
+        self.cells.contains(p)
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &Vec2> {
+        self.cells.iter()
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell edges which are not shared with another cell of the region.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|&p| self.outside_neighbors(p).count())
+            .sum()
+    }
+
+    /// Number of straight fence sides, equal to the number of corners.
+    pub fn sides(&self) -> usize {
+        const CORNERS: [(i32, i32); 4] = [(-1, -1), (1, -1), (1, 1), (-1, 1)];
+
+        self.cells
+            .iter()
+            .map(|&p| {
+                CORNERS
+                    .iter()
+                    .filter(|&&(dx, dy)| {
+                        let horizontal = self.contains(&(p + (dx, 0).into()));
+                        let vertical = self.contains(&(p + (0, dy).into()));
+                        let diagonal = self.contains(&(p + (dx, dy).into()));
+
+                        // outer corner or inner corner
+                        (!horizontal && !vertical) || (horizontal && vertical && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Top left and bottom right corner, both inclusive.
+    pub fn bounding_box(&self) -> (Vec2, Vec2) {
+        let min_x = self.cells.iter().map(|p| p.x).min().unwrap_or(0);
+        let min_y = self.cells.iter().map(|p| p.y).min().unwrap_or(0);
+        let max_x = self.cells.iter().map(|p| p.x).max().unwrap_or(0);
+        let max_y = self.cells.iter().map(|p| p.y).max().unwrap_or(0);
+
+        ((min_x, min_y).into(), (max_x, max_y).into())
+    }
+
+    /// Cells with at least one side on the perimeter.
+    pub fn boundary(&self) -> impl Iterator<Item = &Vec2> {
+        self.cells
+            .iter()
+            .filter(|&&p| self.outside_neighbors(p).next().is_some())
+    }
+
+    fn outside_neighbors(&self, p: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        Topology::Orthogonal
+            .offsets()
+            .iter()
+            .map(move |&offset| p + offset)
+            .filter(|neighbor| !self.contains(neighbor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(cells: &[(i32, i32)]) -> Region {
+        Region::new(0, cells.iter().map(|&p| p.into()).collect())
+    }
+
+    #[test]
+    fn test_square() {
+        let square = region(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+
+        assert_eq!(square.area(), 4);
+        assert_eq!(square.perimeter(), 8);
+        assert_eq!(square.sides(), 4);
+        assert_eq!(square.bounding_box(), ((0, 0).into(), (1, 1).into()));
+        assert_eq!(square.boundary().count(), 4);
+    }
+
+    #[test]
+    fn test_ring() {
+        // 3x3 with a hole in the middle, the hole adds 4 inner sides
+        let ring = region(&[(0, 0), (1, 0), (2, 0), (0, 1), (2, 1), (0, 2), (1, 2), (2, 2)]);
+
+        assert_eq!(ring.area(), 8);
+        assert_eq!(ring.perimeter(), 16);
+        assert_eq!(ring.sides(), 8);
+        assert!(!ring.contains(&(1, 1).into()));
+    }
+
+    #[test]
+    fn test_l_shape() {
+        let l_shape = region(&[(5, 5), (5, 6), (5, 7), (6, 7)]);
+
+        assert_eq!(l_shape.perimeter(), 10);
+        assert_eq!(l_shape.sides(), 6);
+        assert_eq!(l_shape.bounding_box(), ((5, 5).into(), (6, 7).into()));
+    }
+
+    #[test]
+    fn test_boundary_skips_inner_cells() {
+        let cells = (0..3).flat_map(|x| (0..3).map(move |y| (x, y))).collect::<Vec<_>>();
+        let block = region(&cells);
+
+        assert_eq!(block.boundary().count(), 8);
+        assert!(block.boundary().all(|p| *p != (1, 1).into()));
+    }
+}
@@ -0,0 +1,154 @@
+use std::{fs::File, io::BufWriter, path::Path};
+
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, Frame, Rgb, RgbImage,
+};
+pub use image::{ImageError, ImageResult};
+
+use crate::Grid;
+
+/// Colour of a cell as red, green and blue.
+pub type Color = [u8; 3];
+
+/// Draws every cell as a `scale` x `scale` square of its colour.
+pub fn to_image<T, F>(grid: &Grid<T>, scale: u32, color: F) -> RgbImage
+    where F: Fn(&T) -> Color
+{
+    let scale = scale.max(1);
+    let size = grid.size();
+    let mut image = RgbImage::new(size.x as u32 * scale, size.y as u32 * scale);
+
+    grid.map(|(point, item)| (point, color(item)))
+        .for_each(|(point, cell_color)| {
+            for dy in 0..scale {
+                for dx in 0..scale {
+                    image.put_pixel(point.x as u32 * scale + dx, point.y as u32 * scale + dy, Rgb(cell_color));
+                }
+            }
+        });
+
+    image
+}
+
+/// Writes the grid as an image, the format (PNG or BMP) follows the extension of `path`.
+pub fn save_image<T, F>(grid: &Grid<T>, path: impl AsRef<Path>, scale: u32, color: F) -> ImageResult<()>
+    where F: Fn(&T) -> Color
+{
+    to_image(grid, scale, color).save(path)
+}
+
+/// Collects frames of a running simulation and saves them as a looping animated GIF.
+///
+/// ```no_run
+/// # use aoclib::{render::GifRecorder, Grid};
+/// # struct Lights { grid: Grid<bool> }
+/// # impl Lights { fn step(&mut self) {} }
+/// # let mut simulation = Lights { grid: Grid::from_string_with_map("#.\n.#", |c| c == '#') };
+/// # let (steps, colors) = (10, [[0, 0, 0], [255, 255, 255]]);
+/// let mut gif = GifRecorder::new(4, 100);
+/// for _ in 0..steps {
+///     simulation.step();
+///     gif.push_frame(&simulation.grid, |&cell| colors[cell as usize]);
+/// }
+/// gif.save("simulation.gif")?;
+/// # Ok::<(), aoclib::render::ImageError>(())
+/// ```
+pub struct GifRecorder {
+    frames: Vec<RgbImage>,
+    scale: u32,
+    frame_delay_ms: u32,
+}
+
+impl GifRecorder {
+    pub fn new(scale: u32, frame_delay_ms: u32) -> Self {
+        GifRecorder {
+            frames: vec![],
+            scale,
+            frame_delay_ms,
+        }
+    }
+
+    pub fn push_frame<T, F>(&mut self, grid: &Grid<T>, color: F)
+        where F: Fn(&T) -> Color
+    {
+        self.frames.push(to_image(grid, self.scale, color));
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> ImageResult<()> {
+        let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        let delay = Delay::from_numer_denom_ms(self.frame_delay_ms, 1);
+        encoder.encode_frames(self.frames.iter().map(|frame| {
+            let rgba = image::DynamicImage::ImageRgb8(frame.clone()).into_rgba8();
+            Frame::from_parts(rgba, 0, 0, delay)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    const BLACK: Color = [0, 0, 0];
+    const WHITE: Color = [255, 255, 255];
+
+    fn board() -> Grid<bool> {
+        Grid::from_string_with_map("#.\n.#", |c| c == '#')
+    }
+
+    fn color(wall: &bool) -> Color {
+        if *wall { BLACK } else { WHITE }
+    }
+
+    #[test]
+    fn test_to_image_scales_cells() {
+        let image = to_image(&board(), 3, color);
+
+        assert_eq!(image.dimensions(), (6, 6));
+        assert_eq!(image.get_pixel(2, 2), &Rgb(BLACK));
+        assert_eq!(image.get_pixel(3, 2), &Rgb(WHITE));
+        assert_eq!(image.get_pixel(5, 5), &Rgb(BLACK));
+    }
+
+    #[test]
+    fn test_save_image_formats() {
+        for extension in ["png", "bmp"] {
+            let path = env::temp_dir().join(format!("aoclib-render-test.{}", extension));
+            save_image(&board(), &path, 2, color).unwrap();
+
+            let image = image::open(&path).unwrap().into_rgb8();
+            assert_eq!(image.dimensions(), (4, 4));
+            assert_eq!(image.get_pixel(0, 3), &Rgb(WHITE));
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_gif_recorder() {
+        let mut gif = GifRecorder::new(1, 50);
+        gif.push_frame(&board(), color);
+        gif.push_frame(&board(), |wall| color(&!wall));
+        assert_eq!(gif.len(), 2);
+
+        let path = env::temp_dir().join("aoclib-render-test.gif");
+        gif.save(&path).unwrap();
+
+        let decoder = image::codecs::gif::GifDecoder::new(std::io::BufReader::new(File::open(&path).unwrap())).unwrap();
+        let frames = image::AnimationDecoder::into_frames(decoder).collect_frames().unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].buffer().get_pixel(0, 0).0, [255, 255, 255, 255]);
+        std::fs::remove_file(path).unwrap();
+    }
+}
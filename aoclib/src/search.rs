@@ -0,0 +1,346 @@
+use std::{collections::{BinaryHeap, HashMap, HashSet}, hash::Hash, iter};
+
+use crate::DijkstraState;
+
+#[derive(Debug)]
+pub struct SearchResult<S> {
+    pub cost: i32,
+    // every state from start to goal, both included
+    pub path: Vec<S>,
+}
+
+/// Cheapest path from `start` to the first state accepted by `is_goal`.
+///
+/// `successors` returns the states reachable from a state together with the cost of the move.
+/// States are arbitrary, e.g. `(Vec2, Direction)` when turning costs too.
+pub fn dijkstra<S, F, I, G>(start: S, successors: F, is_goal: G) -> Option<SearchResult<S>>
+    where S: Hash + Eq + Clone,
+          F: FnMut(&S) -> I, // successors(state: &S) -> [(next_state, move_cost)]
+          I: IntoIterator<Item = (S, i32)>,
+          G: Fn(&S) -> bool, // is_goal(state: &S)
+{
+    astar(start, successors, is_goal, |_| 0)
+}
+
+/// Dijkstra guided by `heuristic`, the estimated cost from a state to the goal.
+/// The heuristic must never overestimate, otherwise the result is not the cheapest path.
+pub fn astar<S, F, I, G, H>(start: S, mut successors: F, is_goal: G, heuristic: H) -> Option<SearchResult<S>>
+    where S: Hash + Eq + Clone,
+          F: FnMut(&S) -> I, // successors(state: &S) -> [(next_state, move_cost)]
+          I: IntoIterator<Item = (S, i32)>,
+          G: Fn(&S) -> bool, // is_goal(state: &S)
+          H: Fn(&S) -> i32, // heuristic(state: &S)
+{
+    // states are numbered in order of discovery, `dist` and `previous` are indexed by that number
+    let mut ids: HashMap<S, usize> = HashMap::new();
+    let mut states: Vec<S> = vec![];
+    let mut dist: Vec<i32> = vec![];
+    let mut previous: Vec<Option<usize>> = vec![];
+
+    // the queue is ordered by the estimated total cost, `dist` keeps the real one
+    let mut queue: BinaryHeap<DijkstraState> = BinaryHeap::new();
+
+    queue.push(DijkstraState{idx: 0, cost: heuristic(&start)});
+    ids.insert(start.clone(), 0);
+    states.push(start);
+    dist.push(0);
+    previous.push(None);
+
+    while let Some(DijkstraState{idx, cost}) = queue.pop() {
+        // better path exists to this state
+        if cost > dist[idx] + heuristic(&states[idx]) {
+            continue;
+        }
+
+        if is_goal(&states[idx]) {
+            return Some(SearchResult{
+                cost: dist[idx],
+                path: unwind_path(&states, &previous, idx),
+            });
+        }
+
+        for (next, move_cost) in successors(&states[idx]) {
+            let next_cost = dist[idx] + move_cost;
+            let next_idx = *ids.entry(next.clone()).or_insert_with(|| {
+                states.push(next.clone());
+                dist.push(i32::MAX);
+                previous.push(None);
+                states.len() - 1
+            });
+
+            if next_cost < dist[next_idx] {
+                // Relaxation, we have now found a better way
+                dist[next_idx] = next_cost;
+                previous[next_idx] = Some(idx);
+                queue.push(DijkstraState{
+                    idx: next_idx,
+                    cost: next_cost + heuristic(&next),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Every optimal path found by `dijkstra_all`, stored as a DAG of equal-cost predecessors.
+#[derive(Debug)]
+pub struct AllPathsResult<S> {
+    pub cost: i32,
+    states: Vec<S>,
+    dist: Vec<i32>,
+    // all predecessors giving the cheapest cost of a state, empty for the start
+    predecessors: Vec<Vec<usize>>,
+    // every goal state reached with `cost`
+    goals: Vec<usize>,
+}
+
+impl<S: Hash + Eq + Clone> AllPathsResult<S> {
+    /// Optimal paths from start to goal, both included. Their number can grow
+    /// exponentially, use `count` when only the number is needed.
+    pub fn paths(&self) -> impl Iterator<Item = Vec<S>> + '_ {
+        // partial paths are built backwards from the goals
+        let mut stack: Vec<Vec<usize>> = self.goals.iter().map(|&goal| vec![goal]).collect();
+
+        iter::from_fn(move || {
+            while let Some(partial) = stack.pop() {
+                let last = *partial.last().unwrap();
+                if self.predecessors[last].is_empty() {
+                    return Some(partial.iter().rev().map(|&idx| self.states[idx].clone()).collect());
+                }
+
+                for &prev in &self.predecessors[last] {
+                    let mut next = partial.clone();
+                    next.push(prev);
+                    stack.push(next);
+                }
+            }
+
+            None
+        })
+    }
+
+    /// States lying on at least one optimal path.
+    pub fn on_any_path(&self) -> HashSet<S> {
+        let mut visited: HashSet<usize> = self.goals.iter().copied().collect();
+        let mut stack = self.goals.clone();
+
+        while let Some(idx) = stack.pop() {
+            for &prev in &self.predecessors[idx] {
+                if visited.insert(prev) {
+                    stack.push(prev);
+                }
+            }
+        }
+
+        visited.into_iter().map(|idx| self.states[idx].clone()).collect()
+    }
+
+    /// Number of distinct optimal paths.
+    pub fn count(&self) -> u64 {
+        // predecessors are always cheaper, so visiting by cost sees them first
+        let mut order: Vec<usize> = (0..self.states.len()).filter(|&idx| self.dist[idx] != i32::MAX).collect();
+        order.sort_by_key(|&idx| self.dist[idx]);
+
+        let mut ways: Vec<u64> = vec![0; self.states.len()];
+        for idx in order {
+            ways[idx] = if self.predecessors[idx].is_empty() {
+                1
+            } else {
+                self.predecessors[idx].iter().map(|&prev| ways[prev]).sum()
+            };
+        }
+
+        self.goals.iter().map(|&goal| ways[goal]).sum()
+    }
+}
+
+/// Like `dijkstra`, but records every equal-cost predecessor, so all optimal paths
+/// can be recovered. Goal states are not expanded and every goal reached with the
+/// optimal cost is kept. Move costs must be positive.
+pub fn dijkstra_all<S, F, I, G>(start: S, mut successors: F, is_goal: G) -> Option<AllPathsResult<S>>
+    where S: Hash + Eq + Clone,
+          F: FnMut(&S) -> I, // successors(state: &S) -> [(next_state, move_cost)]
+          I: IntoIterator<Item = (S, i32)>,
+          G: Fn(&S) -> bool, // is_goal(state: &S)
+{
+    let mut ids: HashMap<S, usize> = HashMap::new();
+    let mut states: Vec<S> = vec![];
+    let mut dist: Vec<i32> = vec![];
+    let mut predecessors: Vec<Vec<usize>> = vec![];
+    let mut goals: Vec<usize> = vec![];
+    let mut queue: BinaryHeap<DijkstraState> = BinaryHeap::new();
+
+    queue.push(DijkstraState{idx: 0, cost: 0});
+    ids.insert(start.clone(), 0);
+    states.push(start);
+    dist.push(0);
+    predecessors.push(vec![]);
+
+    while let Some(DijkstraState{idx, cost}) = queue.pop() {
+        // better path exists to this state
+        if cost > dist[idx] {
+            continue;
+        }
+
+        // every goal with the optimal cost has been found
+        if goals.first().is_some_and(|&goal| cost > dist[goal]) {
+            break;
+        }
+
+        if is_goal(&states[idx]) {
+            goals.push(idx);
+            continue;
+        }
+
+        for (next, move_cost) in successors(&states[idx]) {
+            let next_cost = cost + move_cost;
+            let next_idx = *ids.entry(next.clone()).or_insert_with(|| {
+                states.push(next.clone());
+                dist.push(i32::MAX);
+                predecessors.push(vec![]);
+                states.len() - 1
+            });
+
+            if next_cost < dist[next_idx] {
+                dist[next_idx] = next_cost;
+                predecessors[next_idx] = vec![idx];
+                queue.push(DijkstraState{idx: next_idx, cost: next_cost});
+            } else if next_cost == dist[next_idx] {
+                // another way with the same cost
+                predecessors[next_idx].push(idx);
+            }
+        }
+    }
+
+    if goals.is_empty() {
+        return None;
+    }
+
+    Some(AllPathsResult{
+        cost: dist[goals[0]],
+        states,
+        dist,
+        predecessors,
+        goals,
+    })
+}
+
+fn unwind_path<S: Clone>(states: &[S], previous: &[Option<usize>], goal: usize) -> Vec<S> {
+    let mut path = vec![states[goal].clone()];
+    let mut next = previous[goal];
+    while let Some(idx) = next {
+        path.push(states[idx].clone());
+        next = previous[idx];
+    }
+    path.reverse();
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // position on a number line, moving costs 1 and turning around costs 10
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    struct Walker {
+        pos: i32,
+        forward: bool,
+    }
+
+    fn successors(walker: &Walker) -> Vec<(Walker, i32)> {
+        let step = if walker.forward { 1 } else { -1 };
+
+        vec![
+            (Walker{pos: walker.pos + step, forward: walker.forward}, 1),
+            (Walker{pos: walker.pos, forward: !walker.forward}, 10),
+        ]
+    }
+
+    #[test]
+    fn test_dijkstra_state_space() {
+        let result = dijkstra(Walker{pos: 0, forward: true}, successors, |w| w.pos == -3).unwrap();
+
+        assert_eq!(result.cost, 13);
+        assert_eq!(result.path.len(), 5);
+        assert_eq!(result.path.first(), Some(&Walker{pos: 0, forward: true}));
+        assert_eq!(result.path[1], Walker{pos: 0, forward: false});
+        assert_eq!(result.path.last(), Some(&Walker{pos: -3, forward: false}));
+    }
+
+    #[test]
+    fn test_dijkstra_start_is_goal() {
+        let result = dijkstra(Walker{pos: 0, forward: true}, successors, |w| w.pos == 0).unwrap();
+
+        assert_eq!(result.cost, 0);
+        assert_eq!(result.path, vec![Walker{pos: 0, forward: true}]);
+    }
+
+    #[test]
+    fn test_dijkstra_unreachable() {
+        let result = dijkstra(
+            0,
+            |&n: &i32| (n < 5).then_some((n + 1, 1)),
+            |&n| n == 10,
+        );
+
+        assert!(result.is_none());
+    }
+
+    // open 3x3 room, moves go right or down only
+    fn room(&(x, y): &(i32, i32)) -> Vec<((i32, i32), i32)> {
+        [(x + 1, y), (x, y + 1)]
+            .into_iter()
+            .filter(|&(x, y)| x < 3 && y < 3)
+            .map(|p| (p, 1))
+            .collect()
+    }
+
+    #[test]
+    fn test_dijkstra_all() {
+        let result = dijkstra_all((0, 0), room, |&p| p == (2, 2)).unwrap();
+
+        assert_eq!(result.cost, 4);
+        assert_eq!(result.count(), 6);
+
+        let mut paths = result.paths().collect::<Vec<_>>();
+        paths.sort();
+        paths.dedup();
+        assert_eq!(paths.len(), 6);
+        assert!(paths.iter().all(|path| path.len() == 5 && path[0] == (0, 0) && path[4] == (2, 2)));
+
+        assert_eq!(result.on_any_path().len(), 9);
+    }
+
+    #[test]
+    fn test_dijkstra_all_cells_on_any_path() {
+        // reaching (1, 0) is optimal only through the top row
+        let result = dijkstra_all((0, 0), room, |&p| p == (2, 0)).unwrap();
+
+        assert_eq!(result.count(), 1);
+        assert_eq!(result.on_any_path(), HashSet::from([(0, 0), (1, 0), (2, 0)]));
+    }
+
+    #[test]
+    fn test_dijkstra_all_multiple_goals() {
+        // both corners are 2 moves away
+        let result = dijkstra_all((0, 0), room, |&(x, y)| x + y == 2).unwrap();
+
+        assert_eq!(result.cost, 2);
+        assert_eq!(result.count(), 4);
+        assert!(dijkstra_all((0, 0), room, |&p| p == (3, 3)).is_none());
+    }
+
+    #[test]
+    fn test_astar_matches_dijkstra() {
+        let goal = |w: &Walker| w.pos == 7;
+        let heuristic = |w: &Walker| (7 - w.pos).abs();
+
+        let result = astar(Walker{pos: 0, forward: false}, successors, goal, heuristic).unwrap();
+        let reference = dijkstra(Walker{pos: 0, forward: false}, successors, goal).unwrap();
+
+        assert_eq!(result.cost, 17);
+        assert_eq!(result.path, reference.path);
+    }
+}
@@ -0,0 +1,280 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Puzzle state advanced one step at a time, e.g. a guard walking through a lab.
+pub trait Simulation {
+    /// Everything deciding how the simulation continues, equal states lead to equal futures.
+    type State: Hash + Eq;
+
+    /// Advances by one step, returns `false` without changing anything once the simulation is over.
+    fn step(&mut self) -> bool;
+
+    fn state(&self) -> Self::State;
+
+    /// Text drawing of the current state.
+    fn render(&self) -> String;
+}
+
+/// The state after `start` steps is the first one to repeat, every `length` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// Finds the cycle with Brent's algorithm, keeping only a few states in memory.
+/// `None` when the simulation ends before any state repeats.
+pub fn find_cycle<S>(simulation: &S) -> Option<Cycle>
+    where S: Simulation + Clone
+{
+    let (_, length) = cycle_length(&mut simulation.clone(), u64::MAX).ok()?;
+
+    // with the hare `length` steps ahead they meet at the start of the cycle
+    let mut tortoise = simulation.clone();
+    let mut hare = simulation.clone();
+    (0..length).for_each(|_| { hare.step(); });
+
+    let mut start = 0;
+    while tortoise.state() != hare.state() {
+        tortoise.step();
+        hare.step();
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Brings the simulation to the state after `steps` steps, skipping whole cycles once one is found,
+/// so `steps` may be as large as 10^12. Returns `false` when the simulation ends earlier.
+pub fn fast_forward<S>(simulation: &mut S, steps: u64) -> bool
+    where S: Simulation
+{
+    match cycle_length(simulation, steps) {
+        Ok((done, length)) => {
+            let remaining = (steps - done) % length as u64;
+            (0..remaining).all(|_| simulation.step())
+        }
+        Err(done) => done == steps,
+    }
+}
+
+// Brent's search for the cycle length, stepping `hare` at most `max_steps` times.
+// Returns the steps taken with the length, `hare` is then on the cycle.
+// Without a cycle only the steps taken are returned.
+fn cycle_length<S>(hare: &mut S, max_steps: u64) -> Result<(u64, usize), u64>
+    where S: Simulation
+{
+    let mut tortoise = hare.state();
+    let mut power = 1;
+    let mut length = 1;
+    let mut done = 0;
+
+    while done < max_steps && hare.step() {
+        done += 1;
+        if hare.state() == tortoise {
+            return Ok((done, length));
+        }
+
+        if power == length {
+            tortoise = hare.state();
+            power *= 2;
+            length = 0;
+        }
+        length += 1;
+    }
+
+    Err(done)
+}
+
+/// Every state of a simulation until the first repeated one, see `record`.
+pub struct History<T> {
+    // states[i] is the state after i steps
+    states: Vec<T>,
+    cycle: Option<Cycle>,
+}
+
+/// Runs the simulation until a state repeats, it ends, or `max_steps` steps were made,
+/// remembering the states in a hash table.
+pub fn record<S>(simulation: &mut S, max_steps: usize) -> History<S::State>
+    where S: Simulation,
+          S::State: Clone,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = simulation.state();
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle { start, length: states.len() - start };
+            return History { states, cycle: Some(cycle) };
+        }
+
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        if states.len() > max_steps || !simulation.step() {
+            return History { states, cycle: None };
+        }
+        state = simulation.state();
+    }
+}
+
+impl<T> History<T> {
+    pub fn cycle(&self) -> Option<Cycle> {
+        self.cycle
+    }
+
+    /// Recorded states, the first repeated state is not recorded twice.
+    pub fn states(&self) -> &[T] {
+        &self.states
+    }
+
+    /// State after `step` steps, also past the recorded ones when a cycle was found.
+    pub fn state_at(&self, step: u64) -> Option<&T> {
+        self.index_at(step).map(|index| &self.states[index])
+    }
+
+    /// Sum of `f` over the states after 0 to `steps - 1` steps, whole cycles are added by multiplication.
+    /// `None` when those states are not known.
+    pub fn sum_over<F>(&self, steps: u64, f: F) -> Option<u64>
+        where F: Fn(&T) -> u64
+    {
+        let sum = |from: usize, to: usize| self.states[from..to].iter().map(&f).sum::<u64>();
+
+        if steps <= self.states.len() as u64 {
+            return Some(sum(0, steps as usize));
+        }
+
+        let Cycle { start, length } = self.cycle?;
+        let in_cycle = steps - start as u64;
+        let full_cycles = in_cycle / length as u64;
+        let rest = (in_cycle % length as u64) as usize;
+
+        Some(sum(0, start) + full_cycles * sum(start, start + length) + sum(start, start + rest))
+    }
+
+    fn index_at(&self, step: u64) -> Option<usize> {
+        if step < self.states.len() as u64 {
+            return Some(step as usize);
+        }
+
+        self.cycle.map(|Cycle { start, length }| {
+            start + ((step - start as u64) % length as u64) as usize
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 5, 2, 3, 4, 5, 2, ...
+    #[derive(Clone)]
+    struct Loop {
+        value: usize,
+        start: usize,
+        length: usize,
+    }
+
+    impl Simulation for Loop {
+        type State = usize;
+
+        fn step(&mut self) -> bool {
+            self.value += 1;
+            if self.value == self.start + self.length {
+                self.value = self.start;
+            }
+            true
+        }
+
+        fn state(&self) -> usize {
+            self.value
+        }
+
+        fn render(&self) -> String {
+            self.value.to_string()
+        }
+    }
+
+    // 0, 1, 2 and stops
+    #[derive(Clone)]
+    struct Line(usize);
+
+    impl Simulation for Line {
+        type State = usize;
+
+        fn step(&mut self) -> bool {
+            if self.0 == 2 {
+                return false;
+            }
+            self.0 += 1;
+            true
+        }
+
+        fn state(&self) -> usize {
+            self.0
+        }
+
+        fn render(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    fn simulation(start: usize, length: usize) -> Loop {
+        Loop { value: 0, start, length }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        assert_eq!(find_cycle(&simulation(2, 4)), Some(Cycle { start: 2, length: 4 }));
+        assert_eq!(find_cycle(&simulation(0, 1)), Some(Cycle { start: 0, length: 1 }));
+        assert_eq!(find_cycle(&simulation(37, 101)), Some(Cycle { start: 37, length: 101 }));
+        assert_eq!(find_cycle(&Line(0)), None);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let mut sim = simulation(2, 4);
+        assert!(fast_forward(&mut sim, 1_000_000_000_000));
+        // the cycle starts at step 2 with state 2
+        assert_eq!(sim.value, 2 + (1_000_000_000_000 - 2) % 4);
+
+        let mut sim = simulation(10, 3);
+        assert!(fast_forward(&mut sim, 7));
+        assert_eq!(sim.value, 7);
+
+        let mut line = Line(0);
+        assert!(fast_forward(&mut line, 2));
+        assert!(!fast_forward(&mut Line(0), 3));
+    }
+
+    #[test]
+    fn test_record() {
+        let history = record(&mut simulation(2, 4), usize::MAX);
+
+        assert_eq!(history.cycle(), Some(Cycle { start: 2, length: 4 }));
+        assert_eq!(history.states(), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(history.state_at(6), Some(&2));
+        assert_eq!(history.state_at(1_000_000_000_001), Some(&(2 + (1_000_000_000_001 - 2) % 4)));
+    }
+
+    #[test]
+    fn test_record_without_cycle() {
+        let history = record(&mut Line(0), usize::MAX);
+        assert_eq!((history.cycle(), history.states().len()), (None, 3));
+        assert_eq!(history.state_at(3), None);
+
+        let history = record(&mut simulation(2, 4), 3);
+        assert_eq!(history.states(), &[0, 1, 2, 3]);
+        assert_eq!(history.sum_over(4, |&v| v as u64), Some(6));
+        assert_eq!(history.sum_over(5, |&v| v as u64), None);
+    }
+
+    #[test]
+    fn test_sum_over() {
+        let history = record(&mut simulation(2, 4), usize::MAX);
+
+        // 0 + 1 + 2 + 3 + 4 + 5 + 2 + 3
+        assert_eq!(history.sum_over(8, |&v| v as u64), Some(20));
+        let naive = (0..1000).map(|i| *history.state_at(i).unwrap() as u64).sum::<u64>();
+        assert_eq!(history.sum_over(1000, |&v| v as u64), Some(naive));
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt::Display;
+
+use crate::ParseResult;
+
+/// Answer produced by a single part of a puzzle.
+///
+/// Most puzzles are answered with a number, the rest (crate letters, CRT
+/// drawings, coordinates) are kept as text.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(num) => write!(f, "{}", num),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        match value.parse::<i128>() {
+            Ok(num) => Answer::Number(num),
+            Err(_) => Answer::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        match value.parse::<i128>() {
+            Ok(num) => Answer::Number(num),
+            Err(_) => Answer::Text(value),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i128::try_from(value) {
+                        Ok(num) => Answer::Number(num),
+                        Err(_) => Answer::Text(value.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Uniform interface of a single day.
+///
+/// `Params` holds the values which differ between the example and the real
+/// puzzle (board sizes, number of steps, ...). Its `Default` must describe
+/// the real puzzle. Days without such values use `()`. Malformed input is
+/// reported as a `ParseError` instead of a panic.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    type Params: Default;
+
+    fn part1(input: &str, params: &Self::Params) -> ParseResult<Answer>;
+    fn part2(input: &str, params: &Self::Params) -> ParseResult<Answer>;
+}
+
+/// Type-erased registry entry of a `Solution` run with its default params.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    pub part1: fn(&str) -> ParseResult<Answer>,
+    pub part2: fn(&str) -> ParseResult<Answer>,
+}
+
+impl Puzzle {
+    pub const fn of<S: Solution>() -> Self {
+        Puzzle {
+            year: S::YEAR,
+            day: S::DAY,
+            part1: run_part1::<S>,
+            part2: run_part2::<S>,
+        }
+    }
+
+    /// Runs the given part, `None` if the part does not exist.
+    pub fn run(&self, part: u8, input: &str) -> Option<ParseResult<Answer>> {
+        match part {
+            1 => Some((self.part1)(input)),
+            2 => Some((self.part2)(input)),
+            _ => None,
+        }
+    }
+}
+
+fn run_part1<S: Solution>(input: &str) -> ParseResult<Answer> {
+    S::part1(input, &S::Params::default())
+}
+
+fn run_part2<S: Solution>(input: &str) -> ParseResult<Answer> {
+    S::part2(input, &S::Params::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::parse_token;
+
+    struct Sum;
+
+    struct SumParams {
+        multiplier: i32,
+    }
+
+    impl Default for SumParams {
+        fn default() -> Self {
+            SumParams { multiplier: 10 }
+        }
+    }
+
+    impl Solution for Sum {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+
+        type Params = SumParams;
+
+        fn part1(input: &str, params: &Self::Params) -> ParseResult<Answer> {
+            input
+                .lines()
+                .map(|l| parse_token::<i32>(input, l, "number").map(|num| num * params.multiplier))
+                .sum::<ParseResult<i32>>()
+                .map(Answer::from)
+        }
+
+        fn part2(input: &str, _: &Self::Params) -> ParseResult<Answer> {
+            Ok(input.lines().collect::<Vec<&str>>().join(",").into())
+        }
+    }
+
+    #[test]
+    fn test_answer_conversion() {
+        assert_eq!(Answer::from("1234"), Answer::Number(1234));
+        assert_eq!(Answer::from("-5".to_string()), Answer::Number(-5));
+        assert_eq!(Answer::from("6,1"), Answer::Text("6,1".to_string()));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+        assert_eq!(Answer::from(42_usize).to_string(), "42");
+    }
+
+    #[test]
+    fn test_puzzle_uses_default_params() {
+        let puzzle = Puzzle::of::<Sum>();
+
+        assert_eq!((puzzle.year, puzzle.day), (2000, 1));
+        assert_eq!(puzzle.run(1, "1\n2"), Some(Ok(Answer::Number(30))));
+        assert_eq!(puzzle.run(2, "1\n2"), Some(Ok(Answer::Text("1,2".to_string()))));
+        assert_eq!(puzzle.run(3, "1\n2"), None);
+        assert_eq!(Sum::part1("1\n2", &SumParams { multiplier: 1 }), Ok(Answer::Number(3)));
+    }
+
+    #[test]
+    fn test_puzzle_surfaces_parse_error() {
+        let puzzle = Puzzle::of::<Sum>();
+        let err = puzzle.run(1, "1\nx").unwrap().unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use crate::{Grid, Vec2};
+
+/// Unbounded grid storing only the cells that were set. Coordinates may be negative,
+/// the bounding box grows with every `put_at`.
+pub struct SparseGrid<T> {
+    data: HashMap<Vec2, T>,
+    // top left and bottom right corner of the bounding box, both inclusive
+    bounds: Option<(Vec2, Vec2)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            data: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    /// Keeps the cells of `grid` accepted by `keep`, e.g. only walls of a maze.
+    pub fn from_grid<F>(grid: &Grid<T>, keep: F) -> Self
+        where T: Clone,
+              F: Fn(&Vec2, &T) -> bool,
+    {
+        let mut sparse = SparseGrid::new();
+        grid.map(|(point, item)| (point, item.clone()))
+            .filter(|(point, item)| keep(point, item))
+            .for_each(|(point, item)| sparse.put_at(point, item));
+
+        sparse
+    }
+
+    /// Dense copy of the bounding box, cells which were never set hold `empty`.
+    /// The top left corner of the bounding box becomes (0, 0), see `origin`.
+    /// `None` when no cell was set, `Grid` has no empty form.
+    pub fn to_grid(&self, empty: T) -> Option<Grid<T>>
+        where T: Clone,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        let origin = self.origin();
+        let size = self.size();
+
+        Some(Grid::new_empty_with_x_y_map(size.x, size.y, |x, y| {
+            self.get_at(&(origin + (x, y).into()))
+                .cloned()
+                .unwrap_or_else(|| empty.clone())
+        }))
+    }
+
+    pub fn get_at(&self, p: &Vec2) -> Option<&T> {
+        self.data.get(p)
+    }
+
+    pub fn put_at(&mut self, point: Vec2, item: T) {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some((min, max)) => (
+                (min.x.min(point.x), min.y.min(point.y)).into(),
+                (max.x.max(point.x), max.y.max(point.y)).into(),
+            ),
+        });
+
+        self.data.insert(point, item);
+    }
+
+    /// Top left corner of the bounding box, (0, 0) for an empty grid.
+    pub fn origin(&self) -> Vec2 {
+        self.bounds.map_or((0, 0).into(), |(min, _)| min)
+    }
+
+    /// Size of the bounding box.
+    pub fn size(&self) -> Vec2 {
+        self.bounds.map_or((0, 0).into(), |(min, max)| max - min + (1, 1).into())
+    }
+
+    /// Number of cells that were set.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Like `Grid::map`, but only over the cells that were set, in no particular order.
+    pub fn map<'a, F, R>(&'a self, f: F) -> impl Iterator<Item = R> + 'a
+        where F: Fn((Vec2, &T)) -> R + 'a
+    {
+        self.data
+            .iter()
+            .map(move |(&point, val)| f((point, val)))
+    }
+
+    /// Draws the bounding box, cells which were never set are drawn as `empty`.
+    pub fn visualization<F>(&self, empty: char, map_func: F) -> String
+        where F: Fn(&T) -> char
+    {
+        let origin = self.origin();
+        let size = self.size();
+
+        (0..size.y)
+            .map(|y| {
+                (0..size.x)
+                    .map(|x| self.get_at(&(origin + (x, y).into())).map_or(empty, &map_func))
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_grows_bounding_box() {
+        let mut grid = SparseGrid::new();
+        assert!(grid.is_empty());
+        assert_eq!(grid.size(), (0, 0).into());
+
+        grid.put_at((2, 1).into(), 'a');
+        assert_eq!((grid.origin(), grid.size()), ((2, 1).into(), (1, 1).into()));
+
+        grid.put_at((-1, -2).into(), 'b');
+        grid.put_at((2, 1).into(), 'c');
+        assert_eq!((grid.origin(), grid.size()), ((-1, -2).into(), (4, 4).into()));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.get_at(&(2, 1).into()), Some(&'c'));
+        assert_eq!(grid.get_at(&(0, 0).into()), None);
+    }
+
+    #[test]
+    fn test_visualization() {
+        let mut grid = SparseGrid::new();
+        grid.put_at((-1, 0).into(), 1);
+        grid.put_at((1, 1).into(), 2);
+
+        assert_eq!(grid.visualization('.', |n| char::from_digit(*n, 10).unwrap()), "1..\n..2");
+        assert_eq!(grid.map(|(_, n)| *n).sum::<u32>(), 3);
+    }
+
+    #[test]
+    fn test_grid_conversion() {
+        let dense = Grid::from_string_with_map("#..\n.#.\n...", |c| c);
+
+        let sparse = SparseGrid::from_grid(&dense, |_, c| *c == '#');
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.size(), (2, 2).into());
+
+        let back = sparse.to_grid('.').unwrap();
+        assert_eq!(back.visualization(|c| *c), "#.\n.#");
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let empty = SparseGrid::from_grid(&Grid::from_string_with_map("..\n..", |c| c), |_, c| *c == '#');
+
+        assert!(empty.to_grid('.').is_none());
+        assert_eq!(empty.visualization('.', |c| *c), "");
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    collections::HashMap,
+    io::{self, IsTerminal},
+};
+
+use crate::{DijkstraResult, Grid, Vec2};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnsiColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl AnsiColor {
+    fn background_code(&self) -> u8 {
+        40 + *self as u8
+    }
+}
+
+struct Layer {
+    name: String,
+    color: AnsiColor,
+    // drawn instead of the base cell, also in plain text
+    symbol: Option<char>,
+}
+
+/// Draws a grid in the terminal with coloured layers on top of the base cells.
+/// Later layers are drawn over earlier ones.
+///
+/// ```no_run
+/// # use aoclib::{terminal::{AnsiColor, TerminalRenderer}, Grid, Vec2};
+/// # let grid = Grid::from_string_with_map("..#\n#..\n...", |c| c == '#');
+/// # let start: Vec2 = (0, 0).into();
+/// # let result = grid.dijkstra(start, (2, 2).into(), |_, wall| !wall, |_, _| 1).unwrap();
+/// TerminalRenderer::new(&grid, |wall| if *wall { '#' } else { '.' })
+///     .path("path", &result, AnsiColor::Green)
+///     .mark("start", [start], AnsiColor::Red, Some('S'))
+///     .with_legend()
+///     .print();
+/// ```
+pub struct TerminalRenderer<'a, T, F> {
+    grid: &'a Grid<T>,
+    base: F,
+    layers: Vec<Layer>,
+    // index of the top layer covering a point
+    points: HashMap<Vec2, usize>,
+    legend: bool,
+}
+
+impl<'a, T, F> TerminalRenderer<'a, T, F>
+    where F: Fn(&T) -> char
+{
+    pub fn new(grid: &'a Grid<T>, base: F) -> Self {
+        TerminalRenderer {
+            grid,
+            base,
+            layers: vec![],
+            points: HashMap::new(),
+            legend: false,
+        }
+    }
+
+    /// Highlights the cells of a search result, drawn as `O` in plain text.
+    pub fn path(self, name: &str, result: &DijkstraResult, color: AnsiColor) -> Self {
+        let points = result.path
+            .iter()
+            .map(|state| self.grid.pos2point(state.idx))
+            .collect::<Vec<Vec2>>();
+
+        self.mark(name, points, color, Some('O'))
+    }
+
+    /// Highlights `points`, replacing their cells with `symbol` when given.
+    pub fn mark<I>(mut self, name: &str, points: I, color: AnsiColor, symbol: Option<char>) -> Self
+        where I: IntoIterator<Item = Vec2>
+    {
+        let layer = self.layers.len();
+        self.layers.push(Layer { name: name.to_string(), color, symbol });
+        self.points.extend(points.into_iter().map(|point| (point, layer)));
+
+        self
+    }
+
+    /// Appends a line per layer describing its colour and symbol.
+    pub fn with_legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
+
+    /// Colours are used only when `colored` is set, otherwise layers show up through their symbols.
+    pub fn render(&self, colored: bool) -> String {
+        let mut lines = self.grid
+            .rows()
+            .map(|row| {
+                row.into_iter()
+                    .map(|(point, item)| {
+                        let base = (self.base)(item);
+                        match self.points.get(&point).map(|&layer| &self.layers[layer]) {
+                            None => base.to_string(),
+                            Some(layer) => Self::cell(layer, layer.symbol.unwrap_or(base), colored),
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>();
+
+        if self.legend {
+            lines.push(String::new());
+            lines.extend(self.layers.iter().map(|layer| {
+                format!("{} {}", Self::cell(layer, layer.symbol.unwrap_or(' '), colored), layer.name)
+            }));
+        }
+
+        lines.join("\n")
+    }
+
+    /// Prints to stdout, coloured only when stdout is a terminal.
+    pub fn print(&self) {
+        println!("{}", self.render(io::stdout().is_terminal()));
+    }
+
+    fn cell(layer: &Layer, symbol: char, colored: bool) -> String {
+        if colored {
+            format!("\x1b[{}m{}\x1b[0m", layer.color.background_code(), symbol)
+        } else {
+            symbol.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Grid<char> {
+        Grid::from_string_with_map("...\n.#.\n...", |c| c)
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let grid = maze();
+        let renderer = TerminalRenderer::new(&grid, |c| *c)
+            .mark("start", [(0, 0).into()], AnsiColor::Red, Some('S'))
+            .mark("seen", [(1, 0).into(), (0, 0).into()], AnsiColor::Blue, None);
+
+        // `seen` is drawn over `start`, but has no symbol
+        assert_eq!(renderer.render(false), "...\n.#.\n...");
+    }
+
+    #[test]
+    fn test_render_path() {
+        let grid = maze();
+        let result = grid.bfs((0, 0).into(), (2, 2).into(), |_, c| *c != '#').unwrap();
+        let renderer = TerminalRenderer::new(&grid, |c| *c)
+            .path("path", &result, AnsiColor::Green)
+            .with_legend();
+
+        let plain = renderer.render(false);
+        assert_eq!(plain.lines().take(3).map(|l| l.matches('O').count()).sum::<usize>(), 4);
+        assert!(plain.ends_with("\n\nO path"));
+
+        let colored = renderer.render(true);
+        assert!(colored.starts_with("\x1b[42mO\x1b[0m"));
+        assert!(colored.ends_with("\x1b[42mO\x1b[0m path"));
+    }
+}
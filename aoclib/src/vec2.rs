@@ -0,0 +1,145 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::{Integer, Signed};
+
+/// Point or offset on a plane. Rotations assume `y` grows downwards, as in grids.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2<N = i32> {
+    pub x: N,
+    pub y: N,
+}
+
+impl<N> From<(N, N)> for Vec2<N> {
+    fn from(value: (N, N)) -> Self {
+        Vec2{
+            x: value.0,
+            y: value.1
+        }
+    }
+}
+
+impl<N: Integer> Vec2<N> {
+    pub fn manhattan(&self, other: &Self) -> N {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    /// Number of king moves between the points, diagonal steps included.
+    pub fn chebyshev(&self, other: &Self) -> N {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    /// Both coordinates replaced by -1, 0 or 1, a single step towards the offset.
+    pub fn signum(&self) -> Self {
+        (self.x.signum(), self.y.signum()).into()
+    }
+}
+
+impl<N: Signed> Vec2<N> {
+    /// Quarter turn clockwise on the screen, up becomes right.
+    pub fn rotate_right(&self) -> Self {
+        (-self.y, self.x).into()
+    }
+
+    /// Quarter turn counter clockwise on the screen, up becomes left.
+    pub fn rotate_left(&self) -> Self {
+        (self.y, -self.x).into()
+    }
+}
+
+impl<N: Integer> Add<Vec2<N>> for Vec2<N> {
+    type Output = Vec2<N>;
+
+    fn add(self, rhs: Vec2<N>) -> Self::Output {
+        (self.x + rhs.x, self.y + rhs.y).into()
+    }
+}
+
+impl<N: Integer> AddAssign<Vec2<N>> for Vec2<N> {
+    fn add_assign(&mut self, rhs: Vec2<N>) {
+        *self = *self + rhs;
+    }
+}
+
+// element-wise
+impl<N: Integer> Mul<Vec2<N>> for Vec2<N> {
+    type Output = Vec2<N>;
+
+    fn mul(self, rhs: Vec2<N>) -> Self::Output {
+        (self.x * rhs.x, self.y * rhs.y).into()
+    }
+}
+
+impl<N: Integer> Mul<N> for Vec2<N> {
+    type Output = Vec2<N>;
+
+    fn mul(self, rhs: N) -> Self::Output {
+        (self.x * rhs, self.y * rhs).into()
+    }
+}
+
+impl<N: Integer> Sub<Vec2<N>> for Vec2<N> {
+    type Output = Vec2<N>;
+
+    fn sub(self, rhs: Vec2<N>) -> Self::Output {
+        (self.x - rhs.x, self.y - rhs.y).into()
+    }
+}
+
+impl<N: Integer> SubAssign<Vec2<N>> for Vec2<N> {
+    fn sub_assign(&mut self, rhs: Vec2<N>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<N: Signed> Neg for Vec2<N> {
+    type Output = Vec2<N>;
+
+    fn neg(self) -> Self::Output {
+        (-self.x, -self.y).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a: Vec2 = (3, -2).into();
+        let b: Vec2 = (1, 4).into();
+
+        assert_eq!(a + b, Vec2{x: 4, y: 2});
+        assert_eq!(a - b, Vec2{x: 2, y: -6});
+        assert_eq!(a * b, Vec2{x: 3, y: -8});
+        assert_eq!(a * 3, Vec2{x: 9, y: -6});
+        assert_eq!(-a, Vec2{x: -3, y: 2});
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut points: Vec<Vec2> = vec![(1, 1).into(), (0, 2).into(), (1, 0).into()];
+        points.sort();
+
+        assert_eq!(points, vec![(0, 2).into(), (1, 0).into(), (1, 1).into()]);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a: Vec2<u64> = (2, 10).into();
+        let b: Vec2<u64> = (7, 4).into();
+
+        assert_eq!(a.manhattan(&b), 11);
+        assert_eq!(a.chebyshev(&b), 6);
+        assert_eq!((b - Vec2::from((1, 1))).signum(), (1, 1).into());
+    }
+
+    #[test]
+    fn test_rotation() {
+        let up: Vec2<i64> = (0, -1).into();
+
+        assert_eq!(up.rotate_right(), (1, 0).into());
+        assert_eq!(up.rotate_right().rotate_right(), (0, 1).into());
+        assert_eq!(up.rotate_left(), (-1, 0).into());
+        assert_eq!((Vec2{x: 5, y: -3} * 2).signum(), (1, -1).into());
+    }
+}
@@ -0,0 +1,119 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::{Integer, Signed};
+
+/// Point or offset in space, see `Vec2`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec3<N = i32> {
+    pub x: N,
+    pub y: N,
+    pub z: N,
+}
+
+impl<N> From<(N, N, N)> for Vec3<N> {
+    fn from(value: (N, N, N)) -> Self {
+        Vec3{
+            x: value.0,
+            y: value.1,
+            z: value.2,
+        }
+    }
+}
+
+impl<N: Integer> Vec3<N> {
+    pub fn manhattan(&self, other: &Self) -> N {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+
+    /// Largest difference of a single coordinate.
+    pub fn chebyshev(&self, other: &Self) -> N {
+        self.x.distance(other.x)
+            .max(self.y.distance(other.y))
+            .max(self.z.distance(other.z))
+    }
+
+    /// Every coordinate replaced by -1, 0 or 1.
+    pub fn signum(&self) -> Self {
+        (self.x.signum(), self.y.signum(), self.z.signum()).into()
+    }
+}
+
+impl<N: Integer> Add<Vec3<N>> for Vec3<N> {
+    type Output = Vec3<N>;
+
+    fn add(self, rhs: Vec3<N>) -> Self::Output {
+        (self.x + rhs.x, self.y + rhs.y, self.z + rhs.z).into()
+    }
+}
+
+impl<N: Integer> AddAssign<Vec3<N>> for Vec3<N> {
+    fn add_assign(&mut self, rhs: Vec3<N>) {
+        *self = *self + rhs;
+    }
+}
+
+// element-wise
+impl<N: Integer> Mul<Vec3<N>> for Vec3<N> {
+    type Output = Vec3<N>;
+
+    fn mul(self, rhs: Vec3<N>) -> Self::Output {
+        (self.x * rhs.x, self.y * rhs.y, self.z * rhs.z).into()
+    }
+}
+
+impl<N: Integer> Mul<N> for Vec3<N> {
+    type Output = Vec3<N>;
+
+    fn mul(self, rhs: N) -> Self::Output {
+        (self.x * rhs, self.y * rhs, self.z * rhs).into()
+    }
+}
+
+impl<N: Integer> Sub<Vec3<N>> for Vec3<N> {
+    type Output = Vec3<N>;
+
+    fn sub(self, rhs: Vec3<N>) -> Self::Output {
+        (self.x - rhs.x, self.y - rhs.y, self.z - rhs.z).into()
+    }
+}
+
+impl<N: Integer> SubAssign<Vec3<N>> for Vec3<N> {
+    fn sub_assign(&mut self, rhs: Vec3<N>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<N: Signed> Neg for Vec3<N> {
+    type Output = Vec3<N>;
+
+    fn neg(self) -> Self::Output {
+        (-self.x, -self.y, -self.z).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a: Vec3<i64> = (1, -2, 3).into();
+        let b: Vec3<i64> = (4, 0, -1).into();
+
+        assert_eq!(a + b, (5, -2, 2).into());
+        assert_eq!(a - b, (-3, -2, 4).into());
+        assert_eq!(a * b, (4, 0, -3).into());
+        assert_eq!(a * 2, (2, -4, 6).into());
+        assert_eq!(-a, (-1, 2, -3).into());
+    }
+
+    #[test]
+    fn test_distances() {
+        let a: Vec3<u8> = (1, 5, 9).into();
+        let b: Vec3<u8> = (4, 5, 2).into();
+
+        assert_eq!(a.manhattan(&b), 10);
+        assert_eq!(a.chebyshev(&b), 7);
+        assert_eq!(Vec3::<i32>::from((-8, 0, 3)).signum(), (-1, 0, 1).into());
+    }
+}
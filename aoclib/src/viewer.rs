@@ -0,0 +1,370 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute, queue,
+    style::Print,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::Simulation;
+
+const MIN_DELAY: Duration = Duration::from_millis(10);
+const MAX_DELAY: Duration = Duration::from_millis(2560);
+// steps between kept snapshots of the simulation
+const CHECKPOINT_INTERVAL: usize = 64;
+const HELP: &str = "space play/pause | right/n step | left/b back | g jump | +/- speed | q quit";
+
+/// Step-through player of a simulation. A snapshot is kept every `CHECKPOINT_INTERVAL`
+/// steps, stepping back re-runs the simulation from the closest earlier one.
+pub struct Viewer<S> {
+    // checkpoints[i] is the state after i * CHECKPOINT_INTERVAL steps
+    checkpoints: Vec<S>,
+    state: S,
+    current: usize,
+    // step whose state cannot step any further, once it was reached
+    last: Option<usize>,
+    playing: bool,
+    delay: Duration,
+}
+
+impl<S> Viewer<S>
+    where S: Simulation + Clone
+{
+    pub fn new(simulation: S) -> Self {
+        Viewer {
+            checkpoints: vec![simulation.clone()],
+            state: simulation,
+            current: 0,
+            last: None,
+            playing: false,
+            delay: Duration::from_millis(160),
+        }
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Number of steps taken to reach the shown state.
+    pub fn current_step(&self) -> usize {
+        self.current
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Whether the shown state is the last one of the simulation.
+    pub fn is_finished(&self) -> bool {
+        self.last == Some(self.current)
+    }
+
+    /// Time between steps while playing.
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Shows the next state by running the simulation one step.
+    pub fn forward(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        let mut next = self.state.clone();
+        if !next.step() {
+            self.last = Some(self.current);
+            return false;
+        }
+
+        self.state = next;
+        self.current += 1;
+        if self.current == self.checkpoints.len() * CHECKPOINT_INTERVAL {
+            self.checkpoints.push(self.state.clone());
+        }
+        true
+    }
+
+    pub fn back(&mut self) -> bool {
+        if self.current == 0 {
+            return false;
+        }
+
+        self.jump_to(self.current - 1);
+        true
+    }
+
+    /// Shows the state after `step` steps, or the last one when the simulation ends earlier.
+    pub fn jump_to(&mut self, step: usize) {
+        if step < self.current {
+            let checkpoint = step / CHECKPOINT_INTERVAL;
+            self.state = self.checkpoints[checkpoint].clone();
+            self.current = checkpoint * CHECKPOINT_INTERVAL;
+        }
+
+        while self.current < step && self.forward() {}
+    }
+
+    pub fn toggle_play(&mut self) {
+        self.playing = !self.playing && !self.is_finished();
+    }
+
+    pub fn faster(&mut self) {
+        self.delay = (self.delay / 2).max(MIN_DELAY);
+    }
+
+    pub fn slower(&mut self) {
+        self.delay = (self.delay * 2).min(MAX_DELAY);
+    }
+
+    /// Called every `delay` while playing, pauses at the end of the simulation.
+    pub fn tick(&mut self) {
+        if self.playing && !self.forward() {
+            self.playing = false;
+        }
+    }
+
+    /// Rendered state followed by a status line.
+    pub fn screen(&self) -> String {
+        let status = match (self.playing, self.is_finished()) {
+            (true, _) => "playing",
+            (false, true) => "finished",
+            (false, false) => "paused",
+        };
+
+        format!(
+            "{}\n\nstep {} | {} | {} ms/step",
+            self.state().render(),
+            self.current,
+            status,
+            self.delay.as_millis()
+        )
+    }
+}
+
+/// Opens the viewer in the terminal until `q` or `Esc` is pressed.
+pub fn run<S>(simulation: S) -> io::Result<()>
+    where S: Simulation + Clone
+{
+    let mut viewer = Viewer::new(simulation);
+    let mut stdout = io::stdout();
+
+    terminal::enable_raw_mode()?;
+    let _restore = RestoreTerminal;
+    execute!(stdout, EnterAlternateScreen, cursor::Hide)?;
+
+    event_loop(&mut viewer, &mut stdout)
+}
+
+// leaves raw mode and the alternate screen however `run` ends, a panicking simulation included
+struct RestoreTerminal;
+
+impl Drop for RestoreTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn event_loop<S, W>(viewer: &mut Viewer<S>, out: &mut W) -> io::Result<()>
+    where S: Simulation + Clone,
+          W: Write,
+{
+    // digits typed after `g`
+    let mut jump: Option<String> = None;
+
+    loop {
+        draw(viewer, jump.as_deref(), out)?;
+
+        if viewer.is_playing() && !event::poll(viewer.delay())? {
+            viewer.tick();
+            continue;
+        }
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+
+        if let Some(digits) = jump.as_mut() {
+            match key.code {
+                KeyCode::Char(c) if c.is_ascii_digit() => digits.push(c),
+                KeyCode::Backspace => { digits.pop(); },
+                KeyCode::Enter => {
+                    if let Ok(step) = digits.parse() {
+                        viewer.jump_to(step);
+                    }
+                    jump = None;
+                }
+                _ => jump = None,
+            }
+            continue;
+        }
+
+        if !handle_key(viewer, key, &mut jump) {
+            return Ok(());
+        }
+    }
+}
+
+// returns false when the viewer should be closed
+fn handle_key<S>(viewer: &mut Viewer<S>, key: KeyEvent, jump: &mut Option<String>) -> bool
+    where S: Simulation + Clone
+{
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => return false,
+        KeyCode::Char(' ') => viewer.toggle_play(),
+        KeyCode::Right | KeyCode::Char('n') => { viewer.forward(); },
+        KeyCode::Left | KeyCode::Char('b') => { viewer.back(); },
+        KeyCode::Char('g') => *jump = Some(String::new()),
+        KeyCode::Char('+') | KeyCode::Char('=') => viewer.faster(),
+        KeyCode::Char('-') => viewer.slower(),
+        _ => {}
+    }
+
+    true
+}
+
+fn draw<S, W>(viewer: &Viewer<S>, jump: Option<&str>, out: &mut W) -> io::Result<()>
+    where S: Simulation + Clone,
+          W: Write,
+{
+    queue!(out, Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+
+    // raw mode does not return the carriage on a new line
+    for line in viewer.screen().lines() {
+        queue!(out, Print(line), cursor::MoveToNextLine(1))?;
+    }
+    match jump {
+        Some(digits) => queue!(out, Print(format!("jump to step: {}_", digits)))?,
+        None => queue!(out, Print(HELP))?,
+    }
+
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // counts up to `limit`
+    #[derive(Clone)]
+    struct Counter {
+        value: usize,
+        limit: usize,
+    }
+
+    impl Simulation for Counter {
+        type State = usize;
+
+        fn step(&mut self) -> bool {
+            if self.value == self.limit {
+                return false;
+            }
+
+            self.value += 1;
+            true
+        }
+
+        fn state(&self) -> usize {
+            self.value
+        }
+
+        fn render(&self) -> String {
+            self.value.to_string()
+        }
+    }
+
+    fn viewer(limit: usize) -> Viewer<Counter> {
+        Viewer::new(Counter { value: 0, limit })
+    }
+
+    #[test]
+    fn test_step_forward_and_back() {
+        let mut viewer = viewer(2);
+
+        assert!(!viewer.back());
+        assert!(viewer.forward());
+        assert!(viewer.forward());
+        assert!(!viewer.forward());
+        assert!(viewer.is_finished());
+        assert_eq!(viewer.state().value, 2);
+
+        assert!(viewer.back());
+        assert_eq!((viewer.current_step(), viewer.state().value), (1, 1));
+        assert!(!viewer.is_finished());
+        assert!(viewer.forward());
+        assert!(viewer.is_finished());
+    }
+
+    #[test]
+    fn test_jump_to() {
+        let mut viewer = viewer(10);
+
+        viewer.jump_to(7);
+        assert_eq!(viewer.state().value, 7);
+
+        viewer.jump_to(3);
+        assert_eq!(viewer.state().value, 3);
+
+        // stops at the last state
+        viewer.jump_to(100);
+        assert_eq!((viewer.current_step(), viewer.state().value), (10, 10));
+        assert!(viewer.is_finished());
+    }
+
+    #[test]
+    fn test_snapshots_are_sparse() {
+        let mut viewer = viewer(1000);
+
+        viewer.jump_to(1000);
+        assert_eq!(viewer.checkpoints.len(), 1000 / CHECKPOINT_INTERVAL + 1);
+
+        // earlier states are run again from the closest snapshot
+        assert!(viewer.back());
+        assert_eq!((viewer.current_step(), viewer.state().value), (999, 999));
+        viewer.jump_to(130);
+        assert_eq!((viewer.current_step(), viewer.state().value), (130, 130));
+        assert_eq!(viewer.checkpoints.len(), 1000 / CHECKPOINT_INTERVAL + 1);
+    }
+
+    #[test]
+    fn test_play_pauses_at_the_end() {
+        let mut viewer = viewer(2);
+
+        viewer.toggle_play();
+        assert!(viewer.is_playing());
+        viewer.tick();
+        viewer.tick();
+        assert!(viewer.is_playing());
+        viewer.tick();
+        assert!(!viewer.is_playing());
+        assert_eq!(viewer.state().value, 2);
+
+        // nothing left to play
+        viewer.toggle_play();
+        assert!(!viewer.is_playing());
+    }
+
+    #[test]
+    fn test_speed_limits() {
+        let mut viewer = viewer(0);
+
+        (0..20).for_each(|_| viewer.faster());
+        assert_eq!(viewer.delay(), MIN_DELAY);
+        (0..20).for_each(|_| viewer.slower());
+        assert_eq!(viewer.delay(), MAX_DELAY);
+    }
+
+    #[test]
+    fn test_screen() {
+        let mut viewer = viewer(5);
+        viewer.jump_to(3);
+
+        assert_eq!(viewer.screen(), "3\n\nstep 3 | paused | 160 ms/step");
+    }
+}